@@ -8,22 +8,37 @@ use crate::generic::i_field::IField;
 use crate::generic::i_primitive_roots::IPrimitiveRoots;
 use crate::generic::i_math::IMath;
 use crate::generic::i_ordered::IOrdered;
+use crate::generic::i_normed::INormed;
 pub mod generic;
 use crate::generic::double_field::DoubleField;
 use crate::generic::int_mod_p::IntModP;
 use crate::generic::i_copiable::ICopiable;
 use crate::generic::int_mod_p::set_modulus;
+use crate::generic::const_int_mod_p::ConstIntModP;
+use crate::generic::montgomery_int_mod_p::MontgomeryIntModP;
+use crate::generic::barrett_int_mod_p::BarrettIntModP;
+use crate::generic::extension_field::ExtensionField;
+use crate::generic::double_double_field::DoubleDoubleField;
+use crate::generic::single_field::SingleField;
+use crate::generic::counting_field::{count_ops, CountingField};
+use crate::specialized::FiniteFFT;
+use std::time::Instant;
+
+// 7 * 2^26 + 1, has roots of unity for every power of two up to 2^26
+const NTT_PRIME: u64 = 469762049;
+// 2^31 - 1, GF(p^2)* has order (p-1)(p+1) = 2^32 * (2^30 - 1), so every power of two up to 2^32 works
+const MERSENNE_31: u64 = 2147483647;
 
 pub struct GenFFT<N>
 where
-    N: IField + IMath + IOrdered + IPrimitiveRoots<N> + ICopiable + fmt::Display,
+    N: IField + IMath + INormed + IPrimitiveRoots<N> + ICopiable + fmt::Display,
 {
     c: N,
 }
 
 impl<N> GenFFT<N>
 where
-    N: IField + IMath + IOrdered + IPrimitiveRoots<N> + ICopiable + fmt::Display,
+    N: IField + IMath + INormed + IPrimitiveRoots<N> + ICopiable + fmt::Display,
 {
     pub fn new(data: N) -> Self {
         Self {
@@ -58,14 +73,10 @@ where
       
         let mut diff = 0.0;
         for i in 0..nd {
-            let d = data[i].copy();
-            let real = d.coerce_to_f64();
-            let imag = d.coerce_to_f64();
-            let real_diff = real - copy[i].coerce_to_f64();
-            let imag_diff = imag - copy[i].coerce_to_f64();
-            diff += real_diff * real_diff + imag_diff * imag_diff;
+            let d = data[i].s(&copy[i]).norm();
+            diff += d * d;
         }
-        (diff / (nd*2) as f64).sqrt()
+        (diff / nd as f64).sqrt()
 
     }
 
@@ -117,8 +128,8 @@ where
         Self::bitreverse(data);
         //println!("After bitreverse: {}", data.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", "));
 
-        // Precompute roots of unity
-        let roots = self.c.precomputeRootsOfUnity(n as u32, direction);
+        // Roots of unity, cached per size and direction and shared between transforms
+        let roots = self.c.roots_of_unity(n as u32, direction);
         //println!("Roots: {}", roots.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", "));
 
         let mut dual = 1;
@@ -158,13 +169,115 @@ where
         }
     }
 }
+// 10 forward/inverse round trips of GenFFT over one finite field backend, returns seconds
+fn time_finite_fft<N>(zero: N, input: &[u64]) -> f64
+where
+    N: IField + IMath + INormed + IPrimitiveRoots<N> + ICopiable + fmt::Display,
+{
+    let fft = GenFFT::new(zero.copy());
+    let mut data: Vec<N> = input.iter().map(|&x| zero.coerce(x as f64)).collect();
+    let start = Instant::now();
+    for _ in 0..10 {
+        fft.transform(&mut data);
+        fft.inverse(&mut data);
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    for (d, &x) in data.iter().zip(input) {
+        if d.coerce_to_f64() != x as f64 {
+            println!("Round trip mismatch: {} != {}", d, x);
+            break;
+        }
+    }
+    elapsed
+}
+
+// forward transform of (re, im) pairs over ComplexField<T>, returned as double-double so it can
+// be compared with the reference transform
+fn complex_transform<T>(zero: T, input: &[(f64, f64)]) -> Vec<(DoubleDoubleField, DoubleDoubleField)>
+where
+    T: IField + IMath + IOrdered + INormed + ICopiable + fmt::Display,
+    ComplexField<T>: IPrimitiveRoots<ComplexField<T>>,
+{
+    let fft = GenFFT::new(ComplexField::new(zero.copy(), zero.copy()));
+    let mut data: Vec<ComplexField<T>> = input.iter()
+        .map(|&(re, im)| ComplexField::new(zero.coerce(re), zero.coerce(im)))
+        .collect();
+    fft.transform(&mut data);
+    data.iter()
+        .map(|c| (DoubleDoubleField::from_f64(c.re.coerce_to_f64()), DoubleDoubleField::from_f64(c.im.coerce_to_f64())))
+        .collect()
+}
+
+// RMS of |x - reference| over RMS of |reference|
+fn relative_rms_error(x: &[(DoubleDoubleField, DoubleDoubleField)], reference: &[(DoubleDoubleField, DoubleDoubleField)]) -> f64 {
+    let mut diff = 0.0;
+    let mut norm = 0.0;
+    for ((xr, xi), (rr, ri)) in x.iter().zip(reference) {
+        let dr = xr.s(rr).coerce_to_f64();
+        let di = xi.s(ri).coerce_to_f64();
+        diff += dr * dr + di * di;
+        norm += rr.coerce_to_f64() * rr.coerce_to_f64() + ri.coerce_to_f64() * ri.coerce_to_f64();
+    }
+    (diff / norm).sqrt()
+}
+
+// wall time of one transform + inverse over N, next to the field operations the same round trip does
+fn op_count_report<N>(name: &str, mut data: Vec<N>)
+where
+    N: IField + IMath + INormed + IPrimitiveRoots<N> + ICopiable + fmt::Display,
+{
+    let zero = data[0].zero();
+    let mut counted: Vec<CountingField<N>> = data.iter().map(|x| CountingField::new(x.copy())).collect();
+    let fft = GenFFT::new(zero.copy());
+    let start = Instant::now();
+    fft.transform(&mut data);
+    fft.inverse(std::hint::black_box(&mut data));
+    let elapsed = start.elapsed().as_secs_f64();
+    let counting_fft = GenFFT::new(CountingField::new(zero));
+    let (_, counts) = count_ops(|| {
+        counting_fft.transform(&mut counted);
+        counting_fft.inverse(&mut counted);
+    });
+    println!("{:<26} {:.6}s  {} ops ({})", name, elapsed, counts.total(), counts);
+}
+
+// schoolbook product written only against num_traits, nothing from the IField API
+fn num_poly_mul<N: num_traits::Num + Clone>(a: &[N], b: &[N]) -> Vec<N> {
+    let mut product = vec![N::zero(); a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            product[i + j] = product[i + j].clone() + x.clone() * y.clone();
+        }
+    }
+    product
+}
+
+// FFT convolution of a and b zero padded to n
+fn fft_poly_mul<N>(a: &[N], b: &[N], n: usize) -> Vec<N>
+where
+    N: IField + IMath + INormed + IPrimitiveRoots<N> + ICopiable + fmt::Display,
+{
+    let fft = GenFFT::new(N::new_zero());
+    let pad = |x: &[N]| -> Vec<N> { (0..n).map(|i| if i < x.len() { x[i].copy() } else { N::new_zero() }).collect() };
+    let mut data1 = pad(a);
+    let mut data2 = pad(b);
+    fft.transform(&mut data1);
+    fft.transform(&mut data2);
+    let mut product: Vec<N> = data1.iter().zip(&data2).map(|(x, y)| x.m(y)).collect();
+    fft.inverse(&mut product);
+    product
+}
+
 fn main() {
-    // let mode = 0 be for testing
-    let mode = 1;
+    let args: Vec<String> = std::env::args().collect();
+    // let mode = 0 be for testing, selected by giving arg 3 = test (listed below)
+    let mode = if args.len() > 3 { 0 } else { 1 };
     if mode != 0 { 
         // arg 1 = size (N = power of 2)
-        // arg 2 = field type (0 = finite field, 1 = complex field)
-        let args: Vec<String> = std::env::args().collect();
+        // arg 2 = field type (0 = finite field, 1 = complex field, 2 = montgomery finite field,
+        //                    3 = barrett finite field, 4 = time every finite field backend,
+        //                    5 = GF(p^2) extension field, 6 = complex forward error against double-double,
+        //                    7 = operation counts and wall time per field)
         let n: usize = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(16);
         let field_type: usize = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(0);
         let mut rand = Lcg::new(12345, 1345, 16645, 1013904);
@@ -257,11 +370,114 @@ fn main() {
 
 
         }
+        else if field_type == 2 {
+            let fft = GenFFT::new(MontgomeryIntModP::<NTT_PRIME>::new(0));
+            let mut data1: Vec<MontgomeryIntModP<NTT_PRIME>> = (0..n)
+                .map(|_| MontgomeryIntModP::new(rand.next_int() as u64))
+                .collect();
+            println!("Generic Rust FFT Tests");
+            println!("Rust Generics, Montgomery Finite Field, n={}", n);
+            for i in 0..10 {
+                fft.transform(&mut data1);
+                fft.inverse(&mut data1);
+                println!("Loop {} done", i);
+            }
+        }
+        else if field_type == 3 {
+            let fft = GenFFT::new(BarrettIntModP::<NTT_PRIME>::new(0));
+            let mut data1: Vec<BarrettIntModP<NTT_PRIME>> = (0..n)
+                .map(|_| BarrettIntModP::new(rand.next_int() as u64))
+                .collect();
+            println!("Generic Rust FFT Tests");
+            println!("Rust Generics, Barrett Finite Field, n={}", n);
+            for i in 0..10 {
+                fft.transform(&mut data1);
+                fft.inverse(&mut data1);
+                println!("Loop {} done", i);
+            }
+        }
+        else if field_type == 4 {
+            // every backend gets the same prime and the same input
+            let input: Vec<u64> = (0..n).map(|_| rand.next_int() as u64 % NTT_PRIME).collect();
+            println!("Rust finite field FFT backends, p={}, n={}", NTT_PRIME, n);
+
+            set_modulus(NTT_PRIME);
+            let plain = time_finite_fft(IntModP::new(0), &input);
+            let montgomery = time_finite_fft(MontgomeryIntModP::<NTT_PRIME>::new(0), &input);
+            let barrett = time_finite_fft(BarrettIntModP::<NTT_PRIME>::new(0), &input);
+
+            FiniteFFT::set_modulus(NTT_PRIME as i32);
+            let specialized = FiniteFFT::FFT::new();
+            let mut data: Vec<i64> = input.iter().map(|&x| x as i64).collect();
+            let start = Instant::now();
+            for _ in 0..10 {
+                specialized.transform(&mut data);
+                specialized.inverse(&mut data);
+            }
+            let specialized_time = start.elapsed().as_secs_f64();
+
+            println!("IntModP:            {:.6}s", plain);
+            println!("MontgomeryIntModP:  {:.6}s", montgomery);
+            println!("BarrettIntModP:     {:.6}s", barrett);
+            println!("specialized FFT:    {:.6}s", specialized_time);
+        }
+        else if field_type == 6 {
+            // inputs exactly representable in f32 so every field transforms the same data
+            let input: Vec<(f64, f64)> = (0..n)
+                .map(|_| (rand.next_double() as f32 as f64, rand.next_double() as f32 as f64))
+                .collect();
+            println!("Rust generic complex FFT accuracy against double-double, n={}", n);
+            let reference = complex_transform(DoubleDoubleField::from_f64(0.0), &input);
+
+            let dd_fft = GenFFT::new(ComplexField::new(DoubleDoubleField::from_f64(0.0), DoubleDoubleField::from_f64(0.0)));
+            let mut round_trip: Vec<ComplexField<DoubleDoubleField>> = input.iter()
+                .map(|&(re, im)| ComplexField::new(DoubleDoubleField::from_f64(re), DoubleDoubleField::from_f64(im)))
+                .collect();
+            dd_fft.transform(&mut round_trip);
+            dd_fft.inverse(&mut round_trip);
+            let round_trip: Vec<(DoubleDoubleField, DoubleDoubleField)> = round_trip.into_iter().map(|c| (c.re, c.im)).collect();
+            let exact_input: Vec<(DoubleDoubleField, DoubleDoubleField)> = input.iter()
+                .map(|&(re, im)| (DoubleDoubleField::from_f64(re), DoubleDoubleField::from_f64(im)))
+                .collect();
+
+            println!("DoubleDoubleField round trip error: {:e}", relative_rms_error(&round_trip, &exact_input));
+            println!("SingleField forward error:          {:e}", relative_rms_error(&complex_transform(SingleField::new(0.0), &input), &reference));
+            println!("DoubleField forward error:          {:e}", relative_rms_error(&complex_transform(DoubleField::new(0.0), &input), &reference));
+        }
+        else if field_type == 7 {
+            println!("Rust generic FFT operation counts, n={}", n);
+            let input: Vec<u64> = (0..n).map(|_| rand.next_int() as u64 % NTT_PRIME).collect();
+            set_modulus(NTT_PRIME);
+            op_count_report("IntModP", input.iter().map(|&x| IntModP::new(x)).collect());
+            op_count_report("ComplexField<DoubleField>", input.iter()
+                .map(|&x| ComplexField::new(DoubleField::new(x as f64), DoubleField::new(0.0)))
+                .collect());
+        }
+        else if field_type == 5 {
+            let fft = GenFFT::new(ExtensionField::<MERSENNE_31, 2>::from_int(0));
+            let mut data1: Vec<ExtensionField<MERSENNE_31, 2>> = (0..n)
+                .map(|_| ExtensionField::new([rand.next_int() as u64, rand.next_int() as u64]))
+                .collect();
+            println!("Generic Rust FFT Tests");
+            println!("Rust Generics, GF(p^2) Extension Field, p={}, n={}", MERSENNE_31, n);
+            for i in 0..10 {
+                fft.transform(&mut data1);
+                fft.inverse(&mut data1);
+                println!("Loop {} done", i);
+            }
+        }
 
     }
     else {
-        // test: 0 for finite field, 1 complex field
-        let test = 0;    
+        // test: 0 for finite field, 1 complex field, 2 two prime fields at once (CRT),
+        //       3 exact round trips and convolution over a 62-bit prime,
+        //       4 the same checks plus field identities over GF(p^2), p = 2^31 - 1,
+        //       5 num_traits interop: inputs parsed with Num::from_str_radix, products checked
+        //         against a num_traits-only schoolbook multiply, pow and inv against the IField API
+        //       6 IMath: Tonelli-Shanks against Cipolla over four primes, complex sqrt/exp/ln/powf/conj
+        //       7 roots of unity cache: build against lookup time, forward/inverse tables, FiniteFFT round trip,
+        //       anything else prints Lcg samples. Args 1 and 2 are not used
+        let test: usize = args.get(3).and_then(|s| s.parse().ok()).unwrap_or(0);
         if test == 0 {
             
             let in1 = [38, 0, 44, 87, 6, 45, 22, 93, 0, 0, 0, 0, 0, 0, 0, 0];
@@ -405,6 +621,326 @@ fn main() {
             fft.inverse(&mut product);
             println!("product (after inverse): {}", product.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", "));
         }
+        else if test == 2 {
+            // same convolution as test 0, done in two prime fields in one process and recombined with CRT
+            const P1: u64 = 40961;
+            const P2: u64 = 65537;
+            let in1 = [38, 0, 44, 87, 6, 45, 22, 93, 0, 0, 0, 0, 0, 0, 0, 0];
+            let in2 = [80, 18, 62, 90, 17, 96, 27, 97, 0, 0, 0, 0, 0, 0, 0, 0];
+
+            let fft1 = GenFFT::new(ConstIntModP::<P1>::new(0));
+            let mut a1: Vec<ConstIntModP<P1>> = in1.iter().map(|&x| ConstIntModP::new(x)).collect();
+            let mut b1: Vec<ConstIntModP<P1>> = in2.iter().map(|&x| ConstIntModP::new(x)).collect();
+            fft1.transform(&mut a1);
+            fft1.transform(&mut b1);
+            let mut product1: Vec<ConstIntModP<P1>> = a1.iter().zip(&b1).map(|(x, y)| x.m(y)).collect();
+            fft1.inverse(&mut product1);
+
+            let fft2 = GenFFT::new(ConstIntModP::<P2>::new(0));
+            let mut a2: Vec<ConstIntModP<P2>> = in1.iter().map(|&x| ConstIntModP::new(x)).collect();
+            let mut b2: Vec<ConstIntModP<P2>> = in2.iter().map(|&x| ConstIntModP::new(x)).collect();
+            fft2.transform(&mut a2);
+            fft2.transform(&mut b2);
+            let mut product2: Vec<ConstIntModP<P2>> = a2.iter().zip(&b2).map(|(x, y)| x.m(y)).collect();
+            fft2.inverse(&mut product2);
+
+            // x = r1 + P1 * ((r2 - r1) * P1^-1 mod P2)
+            let p1_inv = ConstIntModP::<P2>::new(1).d(&ConstIntModP::new(P1));
+            let combined: Vec<u64> = product1.iter().zip(&product2).map(|(r1, r2)| {
+                let t = r2.s(&ConstIntModP::new(r1.i)).m(&p1_inv);
+                r1.i + P1 * t.i
+            }).collect();
+            println!("product mod {}: {}", P1, product1.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", "));
+            println!("product mod {}: {}", P2, product2.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", "));
+            println!("product (CRT): {}", combined.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", "));
+        }
+        else if test == 3 {
+            // 29 * 2^57 + 1, products of two residues need the u128 path in IntModP
+            let prime: u64 = 4179340454199820289;
+            set_modulus(prime);
+            let finite_fft = GenFFT::new(IntModP::new(0));
+            let mut failures = 0;
+
+            // round trips must give back the input exactly
+            let mut x: u64 = 12345;
+            for &n in &[2usize, 16, 256, 4096] {
+                let original: Vec<IntModP> = (0..n).map(|_| {
+                    x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                    IntModP::new(x)
+                }).collect();
+                let mut data = original.clone();
+                finite_fft.transform(&mut data);
+                finite_fft.inverse(&mut data);
+                if data != original {
+                    println!("Round trip FAILED for n={}", n);
+                    failures += 1;
+                }
+            }
+
+            // negative integers come in as their residues, -1 as p - 1
+            for value in [-1, -2, -12345, i32::MIN, 0, 7, i32::MAX] {
+                let expected = (value as i64).rem_euclid(prime as i64) as u64;
+                if IntModP::new(0).coerce_from_int(value).i != expected || IntModP::new_from_int(value).i != expected {
+                    println!("coerce_from_int FAILED for {}", value);
+                    failures += 1;
+                }
+            }
+
+            // products and inverses of the largest residues, against u128
+            for &a in &[prime - 1, prime - 2, prime / 2 + 1, 1 << 61, 3] {
+                for &b in &[prime - 1, prime - 3, prime / 3, 2] {
+                    if IntModP::new(a).m(&IntModP::new(b)).i != (a as u128 * b as u128 % prime as u128) as u64 {
+                        println!("Product FAILED for {} * {}", a, b);
+                        failures += 1;
+                    }
+                }
+                let inverse = IntModP::new(1).d(&IntModP::new(a));
+                if IntModP::new(a).m(&inverse).i != 1 {
+                    println!("Inverse FAILED for {}", a);
+                    failures += 1;
+                }
+            }
+
+            // convolution against a schoolbook product computed in u128
+            let in1: Vec<u64> = (0..32).map(|i| (i * 998_244_353 + 7) % 1_000_000_007).collect();
+            let in2: Vec<u64> = (0..32).map(|i| (i * 754_974_721 + 3) % 1_000_000_007).collect();
+            let mut expected = vec![0u64; 64];
+            for i in 0..32 {
+                for j in 0..32 {
+                    let term = (in1[i] as u128 * in2[j] as u128 % prime as u128) as u64;
+                    expected[i + j] = ((expected[i + j] as u128 + term as u128) % prime as u128) as u64;
+                }
+            }
+            let mut data1: Vec<IntModP> = (0..64).map(|i| IntModP::new(if i < 32 { in1[i] } else { 0 })).collect();
+            let mut data2: Vec<IntModP> = (0..64).map(|i| IntModP::new(if i < 32 { in2[i] } else { 0 })).collect();
+            finite_fft.transform(&mut data1);
+            finite_fft.transform(&mut data2);
+            let mut product: Vec<IntModP> = data1.iter().zip(&data2).map(|(a, b)| a.m(b)).collect();
+            finite_fft.inverse(&mut product);
+            if product.iter().map(|x| x.i).collect::<Vec<_>>() != expected {
+                println!("Convolution FAILED");
+                println!("expected: {}", expected.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", "));
+                println!("got:      {}", product.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", "));
+                failures += 1;
+            }
+            println!("62-bit prime NTT checks done, {} failures", failures);
+        }
+        else if test == 4 {
+            type F = ExtensionField<MERSENNE_31, 2>;
+            let finite_fft = GenFFT::new(F::from_int(0));
+            let mut failures = 0;
+            println!("GF(p^2) modulus polynomial (lowest degree first): {:?}", F::from_int(0).modulus_poly());
+
+            let mut x: u64 = 12345;
+            let mut next = || {
+                x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                F::new([x >> 33, (x >> 2) & 0x7fff_ffff])
+            };
+
+            // a * a^-1 = 1, and the Frobenius map has order 2 and fixes exactly GF(p)
+            for _ in 0..1000 {
+                let a = next();
+                if !a.is_zero() && !a.m(&a.inverse()).is_one() {
+                    println!("Inverse FAILED for {}", a);
+                    failures += 1;
+                }
+                if a.frobenius().frobenius() != a || (a.frobenius() == a) != (a.coeffs[1] == 0) {
+                    println!("Frobenius FAILED for {}", a);
+                    failures += 1;
+                }
+            }
+
+            for &n in &[2usize, 16, 256, 4096] {
+                let original: Vec<F> = (0..n).map(|_| next()).collect();
+                let mut data = original.clone();
+                finite_fft.transform(&mut data);
+                finite_fft.inverse(&mut data);
+                if data != original {
+                    println!("Round trip FAILED for n={}", n);
+                    failures += 1;
+                }
+            }
+
+            // convolution against the schoolbook product in the field itself
+            let in1: Vec<F> = (0..32).map(|_| next()).collect();
+            let in2: Vec<F> = (0..32).map(|_| next()).collect();
+            let mut expected: Vec<F> = vec![F::from_int(0); 64];
+            for i in 0..32 {
+                for j in 0..32 {
+                    expected[i + j].ae(&in1[i].m(&in2[j]));
+                }
+            }
+            let mut data1: Vec<F> = (0..64).map(|i| if i < 32 { in1[i].clone() } else { F::from_int(0) }).collect();
+            let mut data2: Vec<F> = (0..64).map(|i| if i < 32 { in2[i].clone() } else { F::from_int(0) }).collect();
+            finite_fft.transform(&mut data1);
+            finite_fft.transform(&mut data2);
+            let mut product: Vec<F> = data1.iter().zip(&data2).map(|(a, b)| a.m(b)).collect();
+            finite_fft.inverse(&mut product);
+            if product != expected {
+                println!("Convolution FAILED");
+                failures += 1;
+            }
+            println!("GF(p^2) NTT checks done, {} failures", failures);
+        }
+        else if test == 5 {
+            set_modulus(NTT_PRIME);
+            let mut failures = 0;
+            let hex = ["1f", "-3", "7ff", "0", "abc", "-1", "12", "ffff"];
+            let a: Vec<IntModP> = hex.iter().map(|s| <IntModP as num_traits::Num>::from_str_radix(s, 16).unwrap()).collect();
+            let b: Vec<IntModP> = hex.iter().rev().map(|s| <IntModP as num_traits::Num>::from_str_radix(s, 16).unwrap()).collect();
+            if a[5] != IntModP::new(NTT_PRIME - 1) {
+                println!("from_str_radix FAILED for -1: {}", a[5]);
+                failures += 1;
+            }
+            if num_poly_mul(&a, &b)[..16] != fft_poly_mul(&a, &b, 16)[..] {
+                println!("IntModP convolution FAILED");
+                failures += 1;
+            }
+            for x in a.iter().filter(|x| !x.is_zero()) {
+                if num_traits::pow(x.copy(), 1000) != x.pow(1000) || !(num_traits::Inv::inv(x) * x).is_one() {
+                    println!("pow/inv FAILED for {}", x);
+                    failures += 1;
+                }
+            }
+
+            type C = ComplexField<DoubleField>;
+            let a: Vec<C> = ["1.5", "-2", "0.25", "3"].iter().map(|s| <C as num_traits::Num>::from_str_radix(s, 10).unwrap()).collect();
+            let b: Vec<C> = a.iter().map(|x| x * &C::new(DoubleField::new(0.0), DoubleField::new(1.0)) + C::new_one()).collect();
+            let expected = num_poly_mul(&a, &b);
+            let product = fft_poly_mul(&a, &b, 8);
+            let max_error = expected.iter().zip(&product)
+                .map(|(x, y)| { let d = x - y; (d.re.d * d.re.d + d.im.d * d.im.d).sqrt() })
+                .fold(0.0, f64::max);
+            if max_error > 1e-12 {
+                println!("Complex convolution FAILED, max error {:e}", max_error);
+                failures += 1;
+            }
+            println!("num_traits interop checks done, {} failures", failures);
+        }
+        else if test == 6 {
+            let mut failures = 0;
+            let mut rand = Lcg::new(12345, 1345, 16645, 1013904);
+            // 119 * 2^23 + 1, 2^61 - 1 (p = 3 mod 4) and 29 * 2^57 + 1
+            for p in [NTT_PRIME, 998244353, 2305843009213693951, 4179340454199820289] {
+                let mut residues = 0;
+                for _ in 0..1000 {
+                    let a = ((rand.next_int() as u32 as u64) << 32 | rand.next_int() as u32 as u64) % p;
+                    let ts = generic::int_mod_p::sqrt_mod_tonelli_shanks(a, p);
+                    let cipolla = generic::int_mod_p::sqrt_mod_cipolla(a, p);
+                    if ts != cipolla {
+                        println!("mod {}: Tonelli-Shanks {:?} and Cipolla {:?} disagree for {}", p, ts, cipolla, a);
+                        failures += 1;
+                    }
+                    match ts {
+                        Some(r) if (r as u128 * r as u128 % p as u128) as u64 != a => {
+                            println!("mod {}: {}^2 != {}", p, r, a);
+                            failures += 1;
+                        }
+                        Some(_) => residues += 1,
+                        None => {}
+                    }
+                }
+                println!("mod {}: {} of 1000 random values are squares", p, residues);
+            }
+            set_modulus(NTT_PRIME);
+            // 3 generates the multiplicative group mod NTT_PRIME, so its odd powers are non-residues
+            let g = IntModP::new(3);
+            if g.try_sqrt().is_some() || g.pow(5).try_sqrt().is_some() || g.pow(6).try_sqrt() != Some(IntModP::new(27)) {
+                println!("IntModP try_sqrt FAILED");
+                failures += 1;
+            }
+
+            type C = ComplexField<DoubleField>;
+            let c = |re: f64, im: f64| C::new(DoubleField::new(re), DoubleField::new(im));
+            let distance = |x: &C, y: &C| { let d = x - y; (d.re.d * d.re.d + d.im.d * d.im.d).sqrt() };
+            if distance(&c(-4.0, 0.0).sqrt(), &c(0.0, 2.0)) > 1e-15 || distance(&c(-3.0, -4.0).sqrt(), &c(1.0, -2.0)) > 1e-15 {
+                println!("Complex principal sqrt FAILED");
+                failures += 1;
+            }
+            for _ in 0..1000 {
+                let z = c(rand.next_double() * 20.0 - 10.0, rand.next_double() * 20.0 - 10.0);
+                let root = z.copy().sqrt();
+                let norm = z.abs().re.d;
+                if root.re.d < 0.0 || distance(&(&root * &root), &z) > 1e-14 * norm {
+                    println!("sqrt FAILED for {}", z);
+                    failures += 1;
+                }
+                if distance(&z.ln().exp(), &z) > 1e-14 * norm || distance(&z.powf(&c(2.0, 0.0)), &(&z * &z)) > 1e-13 * norm * norm {
+                    println!("exp/ln/powf FAILED for {}", z);
+                    failures += 1;
+                }
+                if (&z * &z.conj()).im.d.abs() > 1e-13 * norm * norm {
+                    println!("conj FAILED for {}", z);
+                    failures += 1;
+                }
+            }
+
+            // Gaussian residues mod 2^31 - 1 = 3 mod 4, i.e. GF(p^2): sqrt and conj need no ordering
+            set_modulus(2147483647);
+            type G = ComplexField<IntModP>;
+            for _ in 0..1000 {
+                let a = G::new(IntModP::new(rand.next_int() as u64), IntModP::new(rand.next_int() as u64));
+                let z = &a * &a;
+                let root = z.copy().sqrt();
+                if &root * &root != z || !(&z * &z.conj()).im.is_zero() {
+                    println!("Gaussian residue sqrt/conj FAILED for {}", z);
+                    failures += 1;
+                }
+            }
+            println!("IMath checks done, {} failures", failures);
+        }
+        else if test == 7 {
+            let mut failures = 0;
+            set_modulus(NTT_PRIME);
+            let n = 1u32 << 16;
+            let start = Instant::now();
+            let forward = IntModP::new(0).roots_of_unity(n, -1);
+            let built = start.elapsed().as_secs_f64();
+            let start = Instant::now();
+            let again = IntModP::new(0).roots_of_unity(n, -1);
+            let hit = start.elapsed().as_secs_f64();
+            let inverse = IntModP::new(0).roots_of_unity(n, 1);
+            println!("IntModP n = {}: built in {:.6}s, cached lookup {:.6}s", n, built, hit);
+            if !std::sync::Arc::ptr_eq(&forward, &again) {
+                println!("second lookup was rebuilt");
+                failures += 1;
+            }
+            let w = forward[1].copy();
+            if !w.pow(n as u64).is_one() || w.pow(n as u64 / 2).is_one() || forward[12345] != w.pow(12345) {
+                println!("forward table is not the powers of a primitive root");
+                failures += 1;
+            }
+            if forward.iter().zip(inverse.iter()).any(|(a, b)| !a.m(b).is_one()) {
+                println!("inverse table is not the inverse of the forward table");
+                failures += 1;
+            }
+
+            let z = ComplexField::new(DoubleField::new(0.0), DoubleField::new(0.0));
+            let forward = z.roots_of_unity(1024, -1);
+            let inverse = z.roots_of_unity(1024, 1);
+            for k in 0..1024 {
+                let angle = -2.0 * std::f64::consts::PI * k as f64 / 1024.0;
+                // the inverse table is the forward one reindexed, w^-k = w^(n-k), so it may differ from conj by an ulp
+                let d = &inverse[k] - &forward[k].conj();
+                if forward[k].re.d != angle.cos() || forward[k].im.d != angle.sin() || d.norm() > 1e-15 {
+                    println!("complex root {} FAILED", k);
+                    failures += 1;
+                }
+            }
+
+            // the specialized transform shares the table cache, a round trip checks its roots
+            FiniteFFT::set_modulus(NTT_PRIME as i32);
+            let specialized = FiniteFFT::FFT::new();
+            let input: Vec<i64> = (0..1024).map(|i| i * 7919 % NTT_PRIME as i64).collect();
+            let mut data = input.clone();
+            specialized.transform(&mut data);
+            specialized.inverse(&mut data);
+            if data != input {
+                println!("FiniteFFT round trip FAILED");
+                failures += 1;
+            }
+            println!("{} cached tables, roots of unity checks done, {} failures", crate::generic::roots_of_unity::cached_tables(), failures);
+        }
         else {
             let mut rand = Lcg::new(12345, 1345, 16645, 1013904);
             let mut random_numbers = [0; 10];
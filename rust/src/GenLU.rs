@@ -1,14 +1,25 @@
 use crate::generic::i_field::IField;
+use crate::generic::field_error::FieldError;
 use crate::generic::double_field::DoubleField;
 use crate::generic::single_field::SingleField;
 use crate::generic::i_math::IMath;
 use crate::generic::int_mod_p::IntModP;
 use crate::generic::int_mod_p::set_modulus;
+use crate::generic::montgomery_int_mod_p::MontgomeryIntModP;
+use crate::generic::barrett_int_mod_p::BarrettIntModP;
+use crate::generic::rational_field::RationalField;
+use crate::generic::extension_field::ExtensionField;
+use crate::generic::double_double_field::DoubleDoubleField;
+use crate::generic::interval_field::IntervalField;
+use crate::generic::dual_field::DualField;
+use crate::generic::counting_field::{count_ops, CountingField};
 use crate::generic::complex_field::ComplexField;
 use crate::generic::i_copiable::ICopiable;
-use crate::generic::i_ordered::IOrdered;
+use crate::generic::i_normed::INormed;
 use std::fmt::Display;
 use crate::helpers::lcg::Lcg;
+use crate::specialized::FiniteLU;
+use std::time::Instant;
 pub mod generic;
 
 pub fn solve<U: IField + ICopiable>(
@@ -66,10 +77,10 @@ fn print_vector<T: Display>(b: &Vec<T>) {
 
 
 
-pub fn factor<U: IField + ICopiable + IMath + IOrdered>(
+pub fn factor<U: IField + ICopiable + INormed>(
     a: &mut Vec<Vec<U>>,
     pivot: &mut Vec<usize>,
-) -> i32 {
+) -> Result<(), FieldError> {
     let n = a.len();
     let m = a[0].len();
     let min_mn = std::cmp::min(m, n);
@@ -77,10 +88,10 @@ pub fn factor<U: IField + ICopiable + IMath + IOrdered>(
     for j in 0..min_mn {
         // Find pivot in column j and test for singularity
         let mut jp = j;
-        let mut t = a[j][j].abs();
+        let mut t = a[j][j].norm();
         for i in (j + 1)..m {
-            let ab = a[i][j].abs();
-            if ab.gt(&t) {
+            let ab = a[i][j].norm();
+            if ab > t {
                 jp = i;
                 t = ab;
             }
@@ -89,8 +100,7 @@ pub fn factor<U: IField + ICopiable + IMath + IOrdered>(
 
         // If zero pivot, factorization fails
         if a[jp][j].is_zero() {
-            println!("Matrix is singular");
-            return 1;
+            return Err(FieldError::SingularMatrix { column: j });
         }
 
         // Swap rows j and jp if needed
@@ -100,7 +110,7 @@ pub fn factor<U: IField + ICopiable + IMath + IOrdered>(
 
         // Compute elements j+1:M of jth column
         if j < m - 1 {
-            let recp = a[j][j].one().d(&a[j][j]);
+            let recp = a[j][j].try_inv()?;
             for k in (j + 1)..m {
                 a[k][j] = a[k][j].m(&recp);
             }
@@ -117,17 +127,20 @@ pub fn factor<U: IField + ICopiable + IMath + IOrdered>(
             }
         }
     }
-    0
+    Ok(())
 }
 
-fn run<T: IField + IMath + ICopiable + IOrdered + Display + Clone>(
+fn run<T: IField + IMath + ICopiable + INormed + Display + Clone>(
     mut a: Vec<Vec<T>>,
     mut b: Vec<T>,
     mut pivot: Vec<usize>,
 ) {
     print_matrix(&a);
     let a_copy = a.clone();
-    factor(&mut a, &mut pivot);
+    if let Err(e) = factor(&mut a, &mut pivot) {
+        println!("Factorization failed: {}", e);
+        return;
+    }
     print_matrix(&a);
     println!("b: ");
     print_vector(&b);
@@ -142,7 +155,7 @@ fn run<T: IField + IMath + ICopiable + IOrdered + Display + Clone>(
     let mut rms_diff = 0.0;
     for i in 0..b_copy.len() {
         let diff = b_copy[i].s(&product[i]);
-        let diff_f64 = diff.coerce_to_f64();
+        let diff_f64 = diff.norm();
         rms_diff += diff_f64 * diff_f64;
     }
     rms_diff = (rms_diff / (b_copy.len() as f64)).sqrt();
@@ -150,9 +163,63 @@ fn run<T: IField + IMath + ICopiable + IOrdered + Display + Clone>(
    
 }
 
+// factor and solve a copy of (a, b) in the field of `zero`, entries coerced from f64
+fn solve_in<T: IField + IMath + ICopiable + INormed + Clone>(zero: &T, a: &[Vec<f64>], b: &[f64]) -> Result<Vec<T>, FieldError> {
+    let mut lu: Vec<Vec<T>> = a.iter().map(|row| row.iter().map(|&x| zero.coerce(x)).collect()).collect();
+    let mut x: Vec<T> = b.iter().map(|&x| zero.coerce(x)).collect();
+    let mut pivot: Vec<usize> = vec![0; a.len()];
+    factor(&mut lu, &mut pivot)?;
+    solve(&lu, &pivot, &mut x);
+    Ok(x)
+}
+
+// max |x - reference| / max |reference|, the difference is taken in double-double
+fn forward_error(x: &[f64], reference: &[DoubleDoubleField]) -> f64 {
+    let mut max_diff: f64 = 0.0;
+    let mut max_ref: f64 = 0.0;
+    for (xi, ri) in x.iter().zip(reference) {
+        max_diff = max_diff.max(DoubleDoubleField::from_f64(*xi).s(ri).coerce_to_f64().abs());
+        max_ref = max_ref.max(ri.coerce_to_f64().abs());
+    }
+    max_diff / max_ref
+}
+
+// solves the same system (entries exactly representable in f32) in single, double and
+// double-double precision and reports the forward error of the first two against the third
+fn accuracy_study(name: &str, a: &[Vec<f64>], b: &[f64]) -> Result<(), FieldError> {
+    let reference = solve_in(&DoubleDoubleField::from_f64(0.0), a, b)?;
+    let single: Vec<f64> = solve_in(&SingleField::new(0.0), a, b)?.iter().map(|x| x.coerce_to_f64()).collect();
+    let double: Vec<f64> = solve_in(&DoubleField::new(0.0), a, b)?.iter().map(|x| x.coerce_to_f64()).collect();
+    println!("{}:", name);
+    println!("  x[0] (double-double reference): {}", reference[0]);
+    println!("  SingleField forward error: {:e}", forward_error(&single, &reference));
+    println!("  DoubleField forward error: {:e}", forward_error(&double, &reference));
+    Ok(())
+}
+
+// wall time of one factor + solve in T, next to the field operations the same solve does
+fn op_count_report<T: IField + IMath + ICopiable + INormed + Clone>(name: &str, zero: T, a: &[Vec<f64>], b: &[f64]) -> Result<(), FieldError> {
+    let start = Instant::now();
+    std::hint::black_box(solve_in(&zero, a, b)?);
+    let elapsed = start.elapsed().as_secs_f64();
+    let (solution, counts) = count_ops(|| solve_in(&CountingField::new(zero.copy()), a, b));
+    solution?;
+    println!("{:<26} {:.6}s  {} ops ({})", name, elapsed, counts.total(), counts);
+    Ok(())
+}
+
 fn main() {
     // arg1 = n (matrix size)
-    // arg2 = field (1=SingleField, 2=DoubleField, else=int mod p)
+    // arg2 = field (1=SingleField, 2=DoubleField, 4=montgomery int mod p, 5=barrett int mod p,
+    //              6=rational (exact, prints A*x against b), 7=GF(2^8) with the AES polynomial,
+    //              8=forward error of single/double against a double-double reference,
+    //              9=interval enclosure of the double solution,
+    //              10=derivative of the solution with respect to a[0][1] (DualField),
+    //              11=operation counts and wall time per field,
+    //              12=singular and non-invertible pivots reported as FieldError,
+    //              13=complex double, pivots chosen by modulus (INormed),
+    //              14=int mod p pivot rows and factors checked against specialized FiniteLU,
+    //              else=int mod p)
     // arg3 = complex_bool (0=not complex, 1=complex)
         println!("Test");
     let args: Vec<String> = std::env::args().collect();
@@ -198,7 +265,10 @@ fn main() {
               let mut pivot: Vec<usize> = vec![0; n];
                 let mut a_clone = a.clone();
                 let mut b_clone = b.clone();
-                factor(&mut a_clone, &mut pivot);
+                if let Err(e) = factor(&mut a_clone, &mut pivot) {
+                    println!("Iteration {} failed: {}", i, e);
+                    continue;
+                }
                 solve(&a_clone, &pivot, &mut b_clone);
                 println!("Iteration {} completed", i);
                /*  if (i == 9) {
@@ -231,7 +301,10 @@ fn main() {
                 let mut pivot: Vec<usize> = vec![0; n];
                 let mut a_clone = a.clone();
                 let mut b_clone = b.clone();
-                factor(&mut a_clone, &mut pivot);
+                if let Err(e) = factor(&mut a_clone, &mut pivot) {
+                    println!("Iteration {} failed: {}", i, e);
+                    continue;
+                }
                 solve(&a_clone, &pivot, &mut b_clone);
                 println!("Iteration {} completed", i);
                 /* if (i == 9) {
@@ -240,6 +313,298 @@ fn main() {
                     print_vector(&b_clone);
                 } */
             }   
+        } else if field == 4 {
+            println!("Rust generic montgomery finitefield LU");
+            println!("Matrix size: {}", n);
+            const MODULUS: u64 = 8191;
+            let mut a: Vec<Vec<MontgomeryIntModP<MODULUS>>> = vec![vec![MontgomeryIntModP::new(0); n]; n];
+            for i in 0..n {
+                let mut row_sum = 0;
+                for j in 0..n {
+                    if i != j {
+                        let val = (rand.next_int() as u64) % MODULUS;
+                        a[i][j] = MontgomeryIntModP::new(val);
+                        row_sum += val;
+                    }
+                }
+                a[i][i] = MontgomeryIntModP::new(row_sum + rand.next_int() as u64 + 1);
+            }
+            let b: Vec<MontgomeryIntModP<MODULUS>> = (0..n)
+                .map(|_| MontgomeryIntModP::new(rand.next_int() as u64))
+                .collect();
+            for i in 0..10 {
+                let mut pivot: Vec<usize> = vec![0; n];
+                let mut a_clone = a.clone();
+                let mut b_clone = b.clone();
+                if let Err(e) = factor(&mut a_clone, &mut pivot) {
+                    println!("Iteration {} failed: {}", i, e);
+                    continue;
+                }
+                solve(&a_clone, &pivot, &mut b_clone);
+                println!("Iteration {} completed", i);
+            }
+        } else if field == 5 {
+            println!("Rust generic barrett finitefield LU");
+            println!("Matrix size: {}", n);
+            const MODULUS: u64 = 8191;
+            let mut a: Vec<Vec<BarrettIntModP<MODULUS>>> = vec![vec![BarrettIntModP::new(0); n]; n];
+            for i in 0..n {
+                let mut row_sum = 0;
+                for j in 0..n {
+                    if i != j {
+                        let val = (rand.next_int() as u64) % MODULUS;
+                        a[i][j] = BarrettIntModP::new(val);
+                        row_sum += val;
+                    }
+                }
+                a[i][i] = BarrettIntModP::new(row_sum + rand.next_int() as u64 + 1);
+            }
+            let b: Vec<BarrettIntModP<MODULUS>> = (0..n)
+                .map(|_| BarrettIntModP::new(rand.next_int() as u64))
+                .collect();
+            for i in 0..10 {
+                let mut pivot: Vec<usize> = vec![0; n];
+                let mut a_clone = a.clone();
+                let mut b_clone = b.clone();
+                if let Err(e) = factor(&mut a_clone, &mut pivot) {
+                    println!("Iteration {} failed: {}", i, e);
+                    continue;
+                }
+                solve(&a_clone, &pivot, &mut b_clone);
+                println!("Iteration {} completed", i);
+            }
+        } else if field == 6 {
+            println!("Rust generic rational LU");
+            println!("Matrix size: {}", n);
+            let mut a: Vec<Vec<RationalField>> = vec![vec![RationalField::from_int(0); n]; n];
+            for i in 0..n {
+                for j in 0..n {
+                    a[i][j] = RationalField::from_int((rand.next_int() % 100) as i64 - 50);
+                }
+            }
+            let b: Vec<RationalField> = (0..n)
+                .map(|_| RationalField::from_int((rand.next_int() % 100) as i64))
+                .collect();
+            let pivot: Vec<usize> = vec![0; n];
+            run(a, b, pivot);
+        } else if field == 7 {
+            println!("Rust generic GF(2^8) LU");
+            println!("Matrix size: {}", n);
+            // x^8 + x^4 + x^3 + x + 1
+            ExtensionField::<2, 8>::set_modulus_poly(&[1, 1, 0, 1, 1, 0, 0, 0, 1]);
+            let byte = |v: i32| {
+                let mut coeffs = [0u64; 8];
+                for (bit, c) in coeffs.iter_mut().enumerate() {
+                    *c = ((v >> bit) & 1) as u64;
+                }
+                ExtensionField::<2, 8>::new(coeffs)
+            };
+            let a: Vec<Vec<ExtensionField<2, 8>>> = (0..n)
+                .map(|_| (0..n).map(|_| byte(rand.next_int())).collect())
+                .collect();
+            let b: Vec<ExtensionField<2, 8>> = (0..n).map(|_| byte(rand.next_int())).collect();
+            let pivot: Vec<usize> = vec![0; n];
+            run(a, b, pivot);
+        } else if field == 8 {
+            println!("Rust generic LU accuracy against double-double");
+            println!("Matrix size: {}", n);
+            let random: Vec<Vec<f64>> = (0..n)
+                .map(|_| (0..n).map(|_| (rand.next_double() * 2.0 - 1.0) as f32 as f64).collect())
+                .collect();
+            let hilbert: Vec<Vec<f64>> = (0..n)
+                .map(|i| (0..n).map(|j| (1.0 / (i + j + 1) as f64) as f32 as f64).collect())
+                .collect();
+            let b: Vec<f64> = (0..n).map(|_| rand.next_double() as f32 as f64).collect();
+            for (name, a) in [("random", &random), ("hilbert", &hilbert)] {
+                if let Err(e) = accuracy_study(name, a, &b) {
+                    println!("{}: {}", name, e);
+                }
+            }
+        } else if field == 9 {
+            println!("Rust generic interval double field LU");
+            println!("Matrix size: {}", n);
+            let mut a: Vec<Vec<f64>> = vec![vec![0.0; n]; n];
+            for i in 0..n {
+                let mut row_sum = 0.0;
+                for j in 0..n {
+                    if i != j {
+                        a[i][j] = rand.next_double() * 1000.0;
+                        row_sum += a[i][j];
+                    }
+                }
+                a[i][i] = row_sum + rand.next_double() * 1000.0 + 1.0;
+            }
+            let b: Vec<f64> = (0..n).map(|_| rand.next_double() * 1000.0).collect();
+
+            let zero = IntervalField::point(DoubleField::new(0.0));
+            let (enclosure, double) = match (solve_in(&zero, &a, &b), solve_in(&DoubleField::new(0.0), &a, &b)) {
+                (Ok(enclosure), Ok(double)) => (enclosure, double),
+                (Err(e), _) | (_, Err(e)) => {
+                    println!("Factorization failed: {}", e);
+                    return;
+                }
+            };
+            let mut max_width: f64 = 0.0;
+            let mut max_relative_width: f64 = 0.0;
+            let mut outside = 0;
+            for (x, d) in enclosure.iter().zip(&double) {
+                max_width = max_width.max(x.width());
+                max_relative_width = max_relative_width.max(x.width() / x.midpoint().abs());
+                if !x.contains(d.d) {
+                    outside += 1;
+                }
+            }
+            println!("x[0] enclosure: {}", enclosure[0]);
+            println!("Max enclosure width: {:e}, max relative width: {:e}", max_width, max_relative_width);
+            println!("DoubleField solution components outside the enclosure: {}", outside);
+        } else if field == 10 {
+            println!("Rust generic dual double field LU");
+            println!("Matrix size: {}", n);
+            let (ei, ej) = (0, 1.min(n - 1));
+            let a: Vec<Vec<f64>> = (0..n)
+                .map(|i| (0..n).map(|j| rand.next_double() * 2.0 - 1.0 + if i == j { n as f64 } else { 0.0 }).collect())
+                .collect();
+            let b: Vec<f64> = (0..n).map(|_| rand.next_double()).collect();
+
+            // seed a[ei][ej] as the variable, everything else is a constant
+            let mut lu: Vec<Vec<DualField<DoubleField>>> = a.iter()
+                .map(|row| row.iter().map(|&x| DualField::constant(DoubleField::new(x))).collect())
+                .collect();
+            lu[ei][ej] = DualField::variable(DoubleField::new(a[ei][ej]));
+            let mut x: Vec<DualField<DoubleField>> = b.iter().map(|&v| DualField::constant(DoubleField::new(v))).collect();
+            let mut pivot: Vec<usize> = vec![0; n];
+            if let Err(e) = factor(&mut lu, &mut pivot) {
+                println!("Factorization failed: {}", e);
+                return;
+            }
+            solve(&lu, &pivot, &mut x);
+
+            // central difference of two DoubleField solves as a check
+            let h = 1e-6;
+            let mut a_plus = a.clone();
+            let mut a_minus = a.clone();
+            a_plus[ei][ej] += h;
+            a_minus[ei][ej] -= h;
+            let (x_plus, x_minus) = match (solve_in(&DoubleField::new(0.0), &a_plus, &b), solve_in(&DoubleField::new(0.0), &a_minus, &b)) {
+                (Ok(x_plus), Ok(x_minus)) => (x_plus, x_minus),
+                (Err(e), _) | (_, Err(e)) => {
+                    println!("Factorization failed: {}", e);
+                    return;
+                }
+            };
+            let mut max_difference: f64 = 0.0;
+            for i in 0..n {
+                let finite_difference = (x_plus[i].d - x_minus[i].d) / (2.0 * h);
+                max_difference = max_difference.max((finite_difference - x[i].derivative.d).abs());
+                if i < 4 {
+                    println!("x[{}] = {}, dx/da[{}][{}] = {:e} (central difference {:e})",
+                        i, x[i].value, ei, ej, x[i].derivative.d, finite_difference);
+                }
+            }
+            println!("Max |dual - central difference|: {:e}", max_difference);
+        } else if field == 11 {
+            println!("Rust generic LU operation counts");
+            println!("Matrix size: {}", n);
+            // small non-negative integers so that every field, IntModP included, sees the same matrix
+            let mut a: Vec<Vec<f64>> = vec![vec![0.0; n]; n];
+            for i in 0..n {
+                let mut row_sum = 0.0;
+                for j in 0..n {
+                    if i != j {
+                        a[i][j] = (rand.next_int() % 100) as f64;
+                        row_sum += a[i][j];
+                    }
+                }
+                a[i][i] = row_sum + 1.0;
+            }
+            let b: Vec<f64> = (0..n).map(|_| (rand.next_int() % 100) as f64).collect();
+            set_modulus(2_u64.pow(13) - 1);
+            let reports = [
+                op_count_report("IntModP", IntModP::new(0), &a, &b),
+                op_count_report("DoubleField", DoubleField::new(0.0), &a, &b),
+                op_count_report("ComplexField<DoubleField>", ComplexField::new(DoubleField::new(0.0), DoubleField::new(0.0)), &a, &b),
+            ];
+            for e in reports.iter().filter_map(|r| r.err()) {
+                println!("Factorization failed: {}", e);
+            }
+        } else if field == 12 {
+            println!("Rust generic LU error reporting");
+            let report = |name: &str, result: Result<(), FieldError>| match result {
+                Ok(()) => println!("{}: factored", name),
+                Err(e) => println!("{}: {}", name, e),
+            };
+
+            // the last row is the sum of the first two
+            let mut a: Vec<Vec<RationalField>> = [[1, 2, 3], [4, 5, 6], [5, 7, 9]].iter()
+                .map(|row| row.iter().map(|&v| RationalField::from_int(v)).collect())
+                .collect();
+            report("RationalField, rank 2 of 3", factor(&mut a, &mut vec![0; 3]));
+
+            // mod 8 the pivot 4 is nonzero but has no inverse
+            set_modulus(8);
+            let mut a: Vec<Vec<IntModP>> = [[2, 1], [4, 3]].iter()
+                .map(|row| row.iter().map(|&v| IntModP::new(v)).collect())
+                .collect();
+            report("IntModP mod 8", factor(&mut a, &mut vec![0; 2]));
+
+            // 1 + 2i has norm 1 + 4 = 0 mod 5
+            set_modulus(5);
+            let z = ComplexField::new(IntModP::new(1), IntModP::new(2));
+            report("ComplexField<IntModP> mod 5, 1/(1+2i)", z.try_inv().map(|_| ()));
+            report("IntModP mod 5, 1/0", IntModP::new(1).checked_d(&IntModP::new(0)).map(|_| ()));
+        } else if field == 13 {
+            println!("Rust generic complex double LU");
+            println!("Matrix size: {}", n);
+            // real off-diagonal entries and a purely imaginary dominant diagonal, stored with the
+            // rows reversed so the first pivot has to come from the last row
+            let mut a: Vec<Vec<ComplexField<DoubleField>>> = Vec::with_capacity(n);
+            for i in 0..n {
+                let mut row_sum = 0.0;
+                let mut row: Vec<ComplexField<DoubleField>> = (0..n).map(|j| {
+                    let val = if i != j { rand.next_double() * 1000.0 } else { 0.0 };
+                    row_sum += val;
+                    ComplexField::new(DoubleField::new(val), DoubleField::new(0.0))
+                }).collect();
+                row[i] = ComplexField::new(DoubleField::new(0.0), DoubleField::new(row_sum + 1.0));
+                a.insert(0, row);
+            }
+            let b: Vec<ComplexField<DoubleField>> = (0..n)
+                .map(|_| ComplexField::new(DoubleField::new(rand.next_double() * 1000.0), DoubleField::new(rand.next_double() * 1000.0)))
+                .collect();
+            let mut pivot: Vec<usize> = vec![0; n];
+            let mut lu = a.clone();
+            let mut x = b.clone();
+            if let Err(e) = factor(&mut lu, &mut pivot) {
+                println!("Factorization failed: {}", e);
+                return;
+            }
+            solve(&lu, &pivot, &mut x);
+            let product = multiplyMatrices(a, x);
+            let residual = b.iter().zip(&product).map(|(bi, pi)| bi.s(pi).norm()).fold(0.0, f64::max);
+            println!("Pivot rows: {}", pivot.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(" "));
+            println!("Max |b - A*x|: {:e}", residual);
+        } else if field == 14 {
+            // random residues, so the largest-residue pivot search swaps rows on most columns
+            let modulus: u64 = 2_u64.pow(13) - 1;
+            set_modulus(modulus);
+            FiniteLU::set_modulus(modulus as i32);
+            let mut failures = 0;
+            for size in [2, 3, 5, 8, n] {
+                let a: Vec<Vec<u64>> = (0..size).map(|_| (0..size).map(|_| rand.next_int() as u64 % modulus).collect()).collect();
+                let mut generic: Vec<Vec<IntModP>> = a.iter().map(|row| row.iter().map(|&v| IntModP::new(v)).collect()).collect();
+                let mut specialized: Vec<Vec<i32>> = a.iter().map(|row| row.iter().map(|&v| v as i32).collect()).collect();
+                let mut generic_pivot = vec![0; size];
+                let mut specialized_pivot = vec![0; size];
+                let generic_result = factor(&mut generic, &mut generic_pivot);
+                // a singular matrix has to stop both at the same column
+                let specialized_singular = FiniteLU::factor(&mut specialized, &mut specialized_pivot) != 0;
+                let same_factors = generic.iter().flatten().zip(specialized.iter().flatten()).all(|(g, &s)| g.i == s as u64);
+                if generic_result.is_err() != specialized_singular || generic_pivot != specialized_pivot || !same_factors {
+                    println!("n = {}: pivots {:?} against FiniteLU {:?}, factors {}", size, generic_pivot, specialized_pivot, if same_factors { "equal" } else { "differ" });
+                    failures += 1;
+                }
+            }
+            println!("IntModP LU pivots against FiniteLU checked, {} failures", failures);
         } else {
             println!("Rust generic finitefield LU");
             println!("Matrix size: {}", n);
@@ -267,7 +632,10 @@ fn main() {
                 let mut pivot: Vec<usize> = vec![0; n];
                 let mut a_clone = a.clone();
                 let mut b_clone = b.clone();
-                factor(&mut a_clone, &mut pivot);
+                if let Err(e) = factor(&mut a_clone, &mut pivot) {
+                    println!("Iteration {} failed: {}", i, e);
+                    continue;
+                }
                 solve(&a_clone, &pivot, &mut b_clone);
                 println!("Iteration {} completed", i);
                 /* if (i == 9) {
@@ -5,6 +5,8 @@ use crate::generic::single_field::SingleField;
 use crate::generic::int_mod_p::set_modulus;
 use crate::generic::i_field::IField;
 use crate::generic::i_ordered::IOrdered;
+use crate::generic::counting_field::{count_ops, CountingField};
+use std::time::Instant;
 pub mod generic;
 
 fn integrate<T: IField + IOrdered>(t: &T, num_samples: usize) -> f64 {
@@ -20,9 +22,19 @@ fn integrate<T: IField + IOrdered>(t: &T, num_samples: usize) -> f64 {
     (under_curve as f64 / num_samples as f64) * 4.0
 }
 
+// wall time of integrate over T, next to the field operations the same run does
+fn op_count_report<T: IField + IOrdered>(name: &str, t: T, num_samples: usize) -> f64 {
+    let start = Instant::now();
+    let pi = std::hint::black_box(integrate(&t, num_samples));
+    let elapsed = start.elapsed().as_secs_f64();
+    let (_, counts) = count_ops(|| integrate(&CountingField::new(t), num_samples));
+    println!("{:<12} {:.6}s  {} ops ({})", name, elapsed, counts.total(), counts);
+    pi
+}
+
 fn main() {
     // arg1 = num_samples
-    // arg2 = mode (1=SingleField, 2=DoubleField, else IntModP)
+    // arg2 = mode (1=SingleField, 3=operation counts and wall time per field, else DoubleField)
     let args: Vec<String> = std::env::args().collect();
     let mut num_samples = 1_000_000;
     let mut mode = 0; // 1 for SingleField, else for DoubleField. Don't think IntModP makes much sense here
@@ -40,6 +52,11 @@ fn main() {
         println!("Rust generic singlefield montecarlo");
         pi = integrate(&temp, num_samples);
     }
+    else if mode == 3 {
+        println!("Rust generic montecarlo operation counts");
+        op_count_report("SingleField", SingleField::new(0.0), num_samples);
+        pi = op_count_report("DoubleField", DoubleField::new(0.0), num_samples);
+    }
     else {
         let temp = DoubleField::new(0.0);
         println!("Rust generic doublefield montecarlo");
@@ -0,0 +1,191 @@
+use std::fmt;
+use std::hash::Hash;
+use crate::generic::i_field::IField;
+use crate::generic::i_exponent::IExponent;
+use crate::generic::monomial_order::MonomialOrder;
+use crate::generic::int_mod_p::{get_modulus, IntModP};
+use crate::generic::rational_field::RationalField;
+use crate::generic::double_field::DoubleField;
+use crate::generic::extension_field::ExtensionField;
+use crate::generic::counting_field::CountingField;
+use crate::gen_grobner::Polynomial;
+use num_traits::Signed;
+
+// a coefficient as it reads in a polynomial: whether it is negative, and the rest, so a term
+// prints as "- 3*x" rather than "+ -3*x". The rest is parenthesized when it is a sum
+pub trait PlainCoefficient {
+    fn plain(&self) -> (bool, String);
+}
+
+// symmetric residues, -3..3 mod 7, as Singular and Macaulay2 print them
+impl PlainCoefficient for IntModP {
+    fn plain(&self) -> (bool, String) {
+        let p = get_modulus();
+        if self.i > p / 2 {
+            (true, (p - self.i).to_string())
+        } else {
+            (false, self.i.to_string())
+        }
+    }
+}
+
+impl PlainCoefficient for RationalField {
+    fn plain(&self) -> (bool, String) {
+        let magnitude = RationalField { numerator: self.numerator.abs(), denominator: self.denominator.clone() };
+        (self.numerator.is_negative(), magnitude.to_string())
+    }
+}
+
+impl PlainCoefficient for DoubleField {
+    fn plain(&self) -> (bool, String) {
+        (self.d.is_sign_negative(), self.d.abs().to_string())
+    }
+}
+
+impl<const P: u64, const K: usize> PlainCoefficient for ExtensionField<P, K> {
+    fn plain(&self) -> (bool, String) {
+        (false, self.to_string())
+    }
+}
+
+impl<T: PlainCoefficient> PlainCoefficient for CountingField<T> {
+    fn plain(&self) -> (bool, String) {
+        self.inner.plain()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Notation {
+    // 3/2*x0^2*x1 - 1, which gen_polynomial_parser reads back for exact coefficients
+    #[default]
+    Plain,
+    // \frac{3}{2} x_{0}^{2} x_{1} - 1
+    Latex,
+}
+
+// how polynomials print. Variables past the end of `variables` are named x<i>. Canonical
+// output does not depend on the term order the polynomials are in: terms go largest first in
+// lex, a basis goes sorted, after a vars line if it has variables, so that the same basis always gives the same
+// text. Over the exact fields, IntModP and RationalField, the text reads back through
+// gen_polynomial_parser; DoubleField prints decimals, which the parser does not take.
+// `show_order` puts the term order in a comment line before a nonempty basis
+#[derive(Clone, Debug, Default)]
+pub struct PolynomialFormat {
+    pub variables: Vec<String>,
+    pub notation: Notation,
+    pub canonical: bool,
+    pub show_order: bool,
+}
+
+impl PolynomialFormat {
+    pub fn new<S: AsRef<str>>(variables: &[S]) -> Self {
+        PolynomialFormat { variables: variables.iter().map(|v| v.as_ref().to_string()).collect(), ..Self::default() }
+    }
+
+    fn variable(&self, var: usize) -> String {
+        let name = self.variables.get(var).cloned().unwrap_or_else(|| format!("x{}", var));
+        if self.notation == Notation::Plain {
+            return name;
+        }
+        // a trailing index as a subscript, x12 as x_{12}
+        let stem = name.trim_end_matches(|c: char| c.is_ascii_digit());
+        if stem.is_empty() || stem.len() == name.len() {
+            name
+        } else {
+            format!("{}_{{{}}}", stem, &name[stem.len()..])
+        }
+    }
+
+    // the monomial of `exponents`, empty for 1
+    pub fn monomial<E: IExponent>(&self, exponents: &E) -> String {
+        let factors: Vec<String> = (0..exponents.vars()).filter(|&v| exponents.exponent(v) > 0).map(|v| {
+            match (exponents.exponent(v), self.notation) {
+                (1, _) => self.variable(v),
+                (e, Notation::Plain) => format!("{}^{}", self.variable(v), e),
+                (e, Notation::Latex) => format!("{}^{{{}}}", self.variable(v), e),
+            }
+        }).collect();
+        let separator = if self.notation == Notation::Plain { "*" } else { " " };
+        factors.join(separator)
+    }
+
+    fn magnitude(&self, text: String) -> String {
+        let text = if text.contains('+') || text.contains(' ') { format!("({})", text) } else { text };
+        match (self.notation, text.split_once('/')) {
+            (Notation::Latex, Some((numerator, denominator))) => format!("\\frac{{{}}}{{{}}}", numerator, denominator),
+            _ => text,
+        }
+    }
+
+    pub fn polynomial<C, E, O>(&self, poly: &Polynomial<C, E, O>) -> String
+    where
+        C: IField + Clone + Hash + Eq + fmt::Display + PlainCoefficient,
+        E: IExponent + Clone + Hash + Eq + fmt::Display,
+        O: MonomialOrder,
+    {
+        let mut terms: Vec<_> = poly.terms.iter().filter(|t| !t.coefficient.is_zero()).collect();
+        if self.canonical {
+            terms.sort_by(|a, b| b.exponents.lex_compare(&a.exponents));
+        }
+        if terms.is_empty() {
+            return "0".to_string();
+        }
+        let mut text = String::new();
+        for (k, term) in terms.iter().enumerate() {
+            let (negative, magnitude) = term.coefficient.plain();
+            text.push_str(match (k, negative) {
+                (0, false) => "",
+                (0, true) => "-",
+                (_, false) => " + ",
+                (_, true) => " - ",
+            });
+            let monomial = self.monomial(&term.exponents);
+            let separator = if self.notation == Notation::Plain { "*" } else { " " };
+            match (monomial.is_empty(), magnitude == "1") {
+                (true, _) => text.push_str(&self.magnitude(magnitude)),
+                (false, true) => text.push_str(&monomial),
+                (false, false) => text.push_str(&format!("{}{}{}", self.magnitude(magnitude), separator, monomial)),
+            }
+        }
+        text
+    }
+
+    // one polynomial per line, in Latex as the rows of a gather* environment
+    pub fn basis<C, E, O>(&self, basis: &[Polynomial<C, E, O>]) -> String
+    where
+        C: IField + Clone + Hash + Eq + fmt::Display + PlainCoefficient,
+        E: IExponent + Clone + Hash + Eq + fmt::Display,
+        O: MonomialOrder,
+    {
+        let mut lines: Vec<String> = basis.iter().map(|poly| self.polynomial(poly)).collect();
+        if self.canonical {
+            lines.sort();
+        }
+        let vars = basis.iter().flat_map(|poly| &poly.terms).map(|t| t.exponents.vars()).max().unwrap_or(0).max(self.variables.len());
+        let names: Vec<String> = (0..vars).map(|v| self.variables.get(v).cloned().unwrap_or_else(|| format!("x{}", v))).collect();
+        let mut text = String::new();
+        let comment = if self.notation == Notation::Plain { "#" } else { "%" };
+        // an empty basis has no order to show
+        if let Some(poly) = basis.first().filter(|_| self.show_order) {
+            text.push_str(&format!("{} order {}, variables {}\n", comment, poly.order.name(), names.join(" > ")));
+        }
+        match self.notation {
+            Notation::Plain => {
+                // the parser takes no empty vars line, and without variables there is nothing to order
+                if self.canonical && !names.is_empty() {
+                    text.push_str(&format!("vars {}\n", names.join(", ")));
+                }
+                for line in lines {
+                    text.push_str(&line);
+                    text.push('\n');
+                }
+            }
+            Notation::Latex => {
+                text.push_str("\\begin{gather*}\n");
+                text.push_str(&lines.join(" \\\\\n"));
+                text.push_str("\n\\end{gather*}\n");
+            }
+        }
+        text
+    }
+}
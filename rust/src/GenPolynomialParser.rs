@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::io::Read;
+use crate::generic::i_field::IField;
+use crate::generic::i_exponent::IExponent;
+use crate::generic::monomial_order::MonomialOrder;
+use crate::gen_grobner::{Polynomial, Term};
+use crate::helpers::cyclic_polynomials::cyclic_polynomials;
+
+// a system in text form, one polynomial per line or separated by ';', e.g.
+//
+//     # cyclic 3
+//     vars x0, x1, x2
+//     x0 + x1 + x2
+//     x0*x1 + x1*x2 + x2*x0
+//     x0*x1*x2 - 1
+//
+// Polynomials are sums of products of variables, integers, rationals written as a quotient
+// and parenthesized polynomials, with non-negative integer powers: 3/2*x^2*(y - 1)^3. A
+// quotient must be by a nonzero constant. Coefficients are evaluated in the target field,
+// so 1/7 fails mod 7. Variables are identifiers, x0 > x1 > ... in the order of the optional
+// vars line, by first appearance otherwise. '#' comments out the rest of a line
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    // 1-based, of the character the error was found at
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    // the text of that line
+    pub source: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "line {}, column {}: {}", self.line, self.column, self.message)?;
+        writeln!(f, "    {}", self.source)?;
+        write!(f, "    {}^", " ".repeat(self.column - 1))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// the parsed polynomials as (exponents, coefficient) terms, the exponents one per variable
+// in the order of `variables`, terms largest first in lex and without zero coefficients
+#[derive(Clone, Debug)]
+pub struct PolynomialSystem<C> {
+    pub variables: Vec<String>,
+    pub polynomials: Vec<Vec<(Vec<u32>, C)>>,
+}
+
+impl<C: IField + Clone + Hash + Eq + fmt::Display> PolynomialSystem<C> {
+    // with the exponents built by `exponent`, as cyclic_system_with in gen_grobner
+    pub fn to_polynomials<E, O>(&self, exponent: impl Fn(&[u32]) -> E, order: O) -> Vec<Polynomial<C, E, O>>
+    where
+        E: IExponent + Clone + Hash + Eq + fmt::Display,
+        O: MonomialOrder,
+    {
+        self.polynomials.iter().map(|terms| {
+            Polynomial::with_order(terms.iter().map(|(exps, c)| Term::from_exponents(c.clone(), exponent(exps))).collect(), order.clone())
+        }).collect()
+    }
+
+    pub fn max_exponent(&self) -> u32 {
+        self.polynomials.iter().flatten().flat_map(|(exps, _)| exps.iter().copied()).max().unwrap_or(0)
+    }
+}
+
+// the text of a system file, of stdin for "-"
+pub fn read_system(path: &str) -> std::io::Result<String> {
+    if path == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        Ok(text)
+    } else {
+        std::fs::read_to_string(path)
+    }
+}
+
+// the system a Grobner driver runs, with a name for the output: cyclic n without `arg`, for a
+// number or for cyclic:<n>, otherwise the file `arg` names, - for stdin. A file named like a
+// number is read by its path, ./5
+pub fn driver_system<C: IField + Clone>(arg: Option<&str>, n: usize) -> Result<(String, PolynomialSystem<C>), String> {
+    let cyclic = |n: usize| -> (String, std::io::Result<String>) { (format!("cyclic {}", n), Ok(cyclic_polynomials(n).join("\n"))) };
+    let (name, text) = match arg {
+        None => cyclic(n),
+        Some(arg) => match (arg.strip_prefix("cyclic:"), arg.parse::<usize>()) {
+            (Some(k), _) => cyclic(k.parse().map_err(|_| format!("Expected cyclic:<n> for a number n, found {}", arg))?),
+            (None, Ok(n)) => cyclic(n),
+            (None, Err(_)) => (if arg == "-" { "stdin".to_string() } else { arg.to_string() }, read_system(arg)),
+        },
+    };
+    let text = text.map_err(|e| format!("Reading {} failed: {}", name, e))?;
+    let system = parse_system(&text).map_err(|e| format!("Parsing {} failed: {}", name, e))?;
+    Ok((name, system))
+}
+
+pub fn parse_system<C: IField + Clone>(text: &str) -> Result<PolynomialSystem<C>, ParseError> {
+    let mut parser = Parser { lines: text.lines().collect(), line: 0, column: 0, variables: Vec::new(), declared: false };
+    let mut polynomials = Vec::new();
+    while parser.line < parser.lines.len() {
+        parser.skip_blanks();
+        match parser.peek() {
+            None | Some('#') => parser.next_line(),
+            Some(';') => parser.column += 1,
+            _ if parser.at_keyword("vars") => parser.declare()?,
+            _ => {
+                polynomials.push(parser.sum()?);
+                parser.skip_blanks();
+                match parser.peek() {
+                    None | Some('#') => parser.next_line(),
+                    Some(';') => parser.column += 1,
+                    Some(')') => return Err(parser.error("unmatched ')'".to_string())),
+                    Some(c) => return Err(parser.error(format!("expected '+', '-', '*', '/', '^' or the end of the polynomial, found '{}'", c))),
+                }
+            }
+        }
+    }
+
+    let vars = parser.variables.len();
+    let polynomials = polynomials.into_iter().map(|poly: Sparse<C>| {
+        let mut terms: Vec<(Vec<u32>, C)> = poly.into_iter().filter(|(_, c)| !c.is_zero()).map(|(mut exps, c)| {
+            exps.resize(vars, 0);
+            (exps, c)
+        }).collect();
+        terms.sort_by(|a, b| b.0.cmp(&a.0));
+        terms
+    }).collect();
+    Ok(PolynomialSystem { variables: parser.variables, polynomials })
+}
+
+// the largest exponent a packed exponent field, and the packed total degree, holds
+const MAX_POWER: u32 = u16::MAX as u32;
+
+// exponents without trailing zeros, so ones from before a variable was first seen match
+type Sparse<C> = HashMap<Vec<u32>, C>;
+
+fn constant<C: IField + Clone>(c: C) -> Sparse<C> {
+    HashMap::from([(Vec::new(), c)])
+}
+
+fn add_into<C: IField + Clone>(sum: &mut Sparse<C>, exps: Vec<u32>, c: &C) {
+    match sum.get_mut(&exps) {
+        Some(existing) => existing.ae(c),
+        None => {
+            sum.insert(exps, c.clone());
+        }
+    }
+}
+
+fn multiply<C: IField + Clone>(a: &Sparse<C>, b: &Sparse<C>) -> Sparse<C> {
+    let mut product = HashMap::new();
+    for (ea, ca) in a {
+        for (eb, cb) in b {
+            let mut exps = vec![0; ea.len().max(eb.len())];
+            for (i, e) in exps.iter_mut().enumerate() {
+                *e = ea.get(i).unwrap_or(&0) + eb.get(i).unwrap_or(&0);
+            }
+            add_into(&mut product, exps, &ca.m(cb));
+        }
+    }
+    product
+}
+
+// recursive descent over one line at a time, a polynomial does not continue past its line
+struct Parser<'a> {
+    lines: Vec<&'a str>,
+    line: usize,
+    // in characters
+    column: usize,
+    variables: Vec<String>,
+    declared: bool,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.lines.get(self.line).and_then(|text| text.chars().nth(self.column))
+    }
+
+    fn next_line(&mut self) {
+        self.line += 1;
+        self.column = 0;
+    }
+
+    fn skip_blanks(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.column += 1;
+        }
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        ParseError { line: self.line + 1, column: self.column + 1, message, source: self.lines.get(self.line).unwrap_or(&"").to_string() }
+    }
+
+    fn found(&self) -> String {
+        match self.peek() {
+            None | Some('#') => "the end of the line".to_string(),
+            Some(c) => format!("'{}'", c),
+        }
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        let first = self.peek().filter(|c| c.is_alphabetic() || *c == '_')?;
+        let mut name = first.to_string();
+        self.column += 1;
+        while let Some(c) = self.peek().filter(|c| c.is_alphanumeric() || *c == '_') {
+            name.push(c);
+            self.column += 1;
+        }
+        Some(name)
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        let rest: String = self.lines[self.line].chars().skip(self.column).collect();
+        rest.strip_prefix(keyword).is_some_and(|after| after.starts_with(char::is_whitespace))
+    }
+
+    fn digits(&mut self) -> Option<Vec<u32>> {
+        let mut digits = Vec::new();
+        while let Some(d) = self.peek().and_then(|c| c.to_digit(10)) {
+            digits.push(d);
+            self.column += 1;
+        }
+        (!digits.is_empty()).then_some(digits)
+    }
+
+    // vars x, y, z: the variables in order, once, before any polynomial
+    fn declare(&mut self) -> Result<(), ParseError> {
+        if self.declared || !self.variables.is_empty() {
+            return Err(self.error("the variables must be declared once, before the first polynomial".to_string()));
+        }
+        self.declared = true;
+        self.column += "vars".len();
+        loop {
+            self.skip_blanks();
+            let at = self.column;
+            match self.identifier() {
+                Some(name) if self.variables.contains(&name) => {
+                    self.column = at;
+                    return Err(self.error(format!("variable {} declared twice", name)));
+                }
+                Some(name) => self.variables.push(name),
+                None => return Err(self.error(format!("expected a variable name, found {}", self.found()))),
+            }
+            self.skip_blanks();
+            match self.peek() {
+                Some(',') => self.column += 1,
+                None | Some('#') => break,
+                Some(c) if c.is_alphabetic() || c == '_' => {}
+                Some(c) => return Err(self.error(format!("expected ',' or a variable name, found '{}'", c))),
+            }
+        }
+        self.next_line();
+        Ok(())
+    }
+
+    // sum := ['+' | '-'] product (('+' | '-') product)*
+    fn sum<C: IField + Clone>(&mut self) -> Result<Sparse<C>, ParseError> {
+        let mut sum = HashMap::new();
+        let mut negate = false;
+        self.skip_blanks();
+        if let Some(sign @ ('+' | '-')) = self.peek() {
+            negate = sign == '-';
+            self.column += 1;
+        }
+        loop {
+            let product = self.product::<C>()?;
+            for (exps, c) in product {
+                add_into(&mut sum, exps, &if negate { c.zero().s(&c) } else { c });
+            }
+            self.skip_blanks();
+            match self.peek() {
+                Some(sign @ ('+' | '-')) => {
+                    negate = sign == '-';
+                    self.column += 1;
+                }
+                _ => return Ok(sum),
+            }
+        }
+    }
+
+    // product := power (('*' | '/') power)*, the right of '/' a nonzero constant
+    fn product<C: IField + Clone>(&mut self) -> Result<Sparse<C>, ParseError> {
+        let mut product = self.power::<C>()?;
+        loop {
+            self.skip_blanks();
+            match self.peek() {
+                Some('*') => {
+                    self.column += 1;
+                    product = multiply(&product, &self.power()?);
+                }
+                Some('/') => {
+                    self.column += 1;
+                    self.skip_blanks();
+                    let at = self.column;
+                    let divisor = self.power::<C>()?;
+                    let value = match divisor.iter().filter(|(_, c)| !c.is_zero()).collect::<Vec<_>>()[..] {
+                        [] => C::new_zero(),
+                        [(exps, c)] if exps.iter().all(|&e| e == 0) => c.clone(),
+                        _ => {
+                            self.column = at;
+                            return Err(self.error("can only divide by a constant".to_string()));
+                        }
+                    };
+                    let inverse = value.try_inv().map_err(|e| {
+                        self.column = at;
+                        self.error(format!("cannot divide by this constant: {}", e))
+                    })?;
+                    product = multiply(&product, &constant(inverse));
+                }
+                _ => return Ok(product),
+            }
+        }
+    }
+
+    // power := '-' power | atom ['^' digits], so -x^2 is -(x^2)
+    fn power<C: IField + Clone>(&mut self) -> Result<Sparse<C>, ParseError> {
+        self.skip_blanks();
+        if self.peek() == Some('-') {
+            self.column += 1;
+            let inner = self.power::<C>()?;
+            return Ok(inner.into_iter().map(|(exps, c)| (exps, c.zero().s(&c))).collect());
+        }
+        let base = self.atom::<C>()?;
+        self.skip_blanks();
+        if self.peek() != Some('^') {
+            return Ok(base);
+        }
+        let caret = self.column;
+        self.column += 1;
+        self.skip_blanks();
+        let exponent = self.digits()
+            .ok_or_else(|| self.error(format!("expected a non-negative integer power, found {}", self.found())))?
+            .into_iter().try_fold(0u32, |e, d| e.checked_mul(10)?.checked_add(d))
+            .filter(|&e| e <= MAX_POWER)
+            .ok_or_else(|| {
+                self.column = caret;
+                self.error(format!("power above {}", MAX_POWER))
+            })?;
+        // square and multiply
+        let mut result = constant(C::new_one());
+        let mut square = base;
+        let mut bits = exponent;
+        while bits > 0 {
+            if bits & 1 == 1 {
+                result = multiply(&result, &square);
+            }
+            bits >>= 1;
+            if bits > 0 {
+                square = multiply(&square, &square);
+            }
+        }
+        Ok(result)
+    }
+
+    // atom := digits | variable | '(' sum ')'
+    fn atom<C: IField + Clone>(&mut self) -> Result<Sparse<C>, ParseError> {
+        self.skip_blanks();
+        let at = self.column;
+        if let Some(digits) = self.digits() {
+            // in the field, so a large integer wraps mod p instead of overflowing
+            let ten = C::new_from_int(10);
+            let value = digits.into_iter().fold(C::new_zero(), |value, d| value.m(&ten).a(&C::new_from_int(d as i32)));
+            return Ok(constant(value));
+        }
+        if let Some(name) = self.identifier() {
+            let var = match self.variables.iter().position(|v| *v == name) {
+                Some(var) => var,
+                None if self.declared => {
+                    self.column = at;
+                    return Err(self.error(format!("variable {} is not declared", name)));
+                }
+                None => {
+                    self.variables.push(name);
+                    self.variables.len() - 1
+                }
+            };
+            let mut exps = vec![0; var + 1];
+            exps[var] = 1;
+            return Ok(HashMap::from([(exps, C::new_one())]));
+        }
+        match self.peek() {
+            Some('(') => {
+                self.column += 1;
+                let inner = self.sum()?;
+                self.skip_blanks();
+                if self.peek() != Some(')') {
+                    return Err(self.error(format!("expected ')' to close the '(' at column {}, found {}", at + 1, self.found())));
+                }
+                self.column += 1;
+                Ok(inner)
+            }
+            _ => Err(self.error(format!("expected a number, a variable or '(', found {}", self.found()))),
+        }
+    }
+}
@@ -2,10 +2,16 @@ use crate::generic::i_field::IField;
 use crate::generic::double_field::DoubleField;
 use crate::generic::single_field::SingleField;
 //use crate::generic::i_math::IMath;
-///use crate::generic::int_mod_p::IntModP;
 use crate::generic::int_mod_p::set_modulus;
 use crate::generic::complex_field::ComplexField;
+use crate::generic::interval_field::IntervalField;
+use crate::generic::dual_field::DualField;
+use crate::generic::counting_field::{count_ops, CountingField};
+use crate::generic::i_copiable::ICopiable;
+use crate::generic::int_mod_p::IntModP;
+use std::time::Instant;
 use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Sub};
 use crate::helpers::lcg::Lcg;
 pub mod generic;
 pub fn execute<U: IField + Display + Clone>(omega: U, g: &mut Vec<Vec<U>>, num_iterations: usize) {
@@ -24,14 +30,77 @@ pub fn execute<U: IField + Display + Clone>(omega: U, g: &mut Vec<Vec<U>>, num_i
             let gim1 = g[i - 1].clone();
             let gip1 = g[i + 1].clone();
             for j in 1..nm1 {
-                g[i][j] = omega_over_four.a(&(
+                g[i][j] = omega_over_four.m(&(
                     gim1[j].a(&gip1[j]).a(&g[i][j - 1]).a(&g[i][j + 1])
-                )).a(&one_minus_omega.a(&g[i][j]));
+                )).a(&one_minus_omega.m(&g[i][j]));
             }
         }
     }
 }
 
+// execute in operator notation, same operations in the same order so the grid is bit-for-bit identical
+pub fn execute_ops<U>(omega: U, g: &mut [Vec<U>], num_iterations: usize)
+where
+    U: IField + Display + Clone + for<'r> Add<&'r U, Output = U>,
+    for<'l, 'r> &'l U: Add<&'r U, Output = U> + Mul<&'r U, Output = U> + Div<&'r U, Output = U> + Sub<&'r U, Output = U>,
+{
+    let m = g.len();
+    let n = g[0].len();
+
+    let four = omega.coerce(4.0);
+    let omega_over_four = &omega / &four;
+    let one_minus_omega = &U::one(&omega) - &omega;
+
+    let mm1 = m - 1;
+    let nm1 = n - 1;
+
+    for _ in 0..num_iterations {
+        for i in 1..mm1 {
+            let gim1 = g[i - 1].clone();
+            let gip1 = g[i + 1].clone();
+            for j in 1..nm1 {
+                let neighbours = &gim1[j] + &gip1[j] + &g[i][j - 1] + &g[i][j + 1];
+                g[i][j] = &omega_over_four * &neighbours + &(&one_minus_omega * &g[i][j]);
+            }
+        }
+    }
+}
+
+// runs execute and execute_ops on the same grid and reports mismatches and both timings
+fn ops_report<U>(name: &str, omega: U, hot: U, n: usize, num_iterations: usize)
+where
+    U: IField + ICopiable + Display + Clone + PartialEq + for<'r> Add<&'r U, Output = U>,
+    for<'l, 'r> &'l U: Add<&'r U, Output = U> + Mul<&'r U, Output = U> + Div<&'r U, Output = U> + Sub<&'r U, Output = U>,
+{
+    let mut g = vec![vec![omega.zero(); n]; n];
+    g[0] = vec![hot; n];
+    let mut g_ops = g.clone();
+    let start = Instant::now();
+    execute(omega.clone(), std::hint::black_box(&mut g), num_iterations);
+    let method_time = start.elapsed().as_secs_f64();
+    let start = Instant::now();
+    execute_ops(omega, std::hint::black_box(&mut g_ops), num_iterations);
+    let ops_time = start.elapsed().as_secs_f64();
+    let mismatches = g.iter().flatten().zip(g_ops.iter().flatten()).filter(|(a, b)| a != b).count();
+    println!("{:<26} methods {:.6}s  operators {:.6}s  mismatches {}", name, method_time, ops_time, mismatches);
+}
+
+// wall time of the SOR run over U, next to the field operations the same run does
+fn op_count_report<U: IField + ICopiable + Display + Clone>(name: &str, omega: U, hot: U, n: usize, num_iterations: usize) {
+    let mut g = vec![vec![omega.zero(); n]; n];
+    for j in 0..n {
+        g[0][j] = hot.clone();
+    }
+    let mut counted: Vec<Vec<CountingField<U>>> = g.iter()
+        .map(|row| row.iter().map(|x| CountingField::new(x.clone())).collect())
+        .collect();
+    let start = Instant::now();
+    execute(omega.clone(), std::hint::black_box(&mut g), num_iterations);
+    let elapsed = start.elapsed().as_secs_f64();
+    let (_, counts) = count_ops(|| execute(CountingField::new(omega), &mut counted, num_iterations));
+    println!("{:<26} {:.6}s  {} ops ({})", name, elapsed, counts.total(), counts);
+}
+
 fn print_matrix<T: Display>(a: &Vec<Vec<T>>) {
     for row in a {
         for val in row {
@@ -44,8 +113,12 @@ fn print_matrix<T: Display>(a: &Vec<Vec<T>>) {
 
 fn main() {
     // arg1 = grid size n (nxn)
-    // arg2 = mode (1=SingleField, 2=DoubleField, else IntModP)
+    // arg2 = mode (1=SingleField, 2=DoubleField, 3=interval DoubleField enclosure,
+    //             4=derivative of the grid with respect to omega (DualField),
+    //             5=operation counts and wall time per field,
+    //             6=method API against operator notation, else IntModP)
     // arg3 = complex_bool (0=real, 1=complex)
+    // arg4 = omega for modes 3 and 4 (default 1.5)
     let args = std::env::args().collect::<Vec<String>>();
     let n: usize = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(16);
     let m = n;
@@ -106,6 +179,104 @@ fn main() {
             //println!("\nSteady-state temperature distribution:");
             //print_matrix(&g);
         }
+        else if field_type == 3 {
+            println!("Rust generic interval doublefield SOR");
+            println!("Grid size: {}x{}", m, n);
+            println!("Number of iterations: {}", num_iterations);
+            // an updated point is as wide as omega/4 times its four neighbours plus |1 - omega| times
+            // itself, so for omega > 1 the enclosure widens every sweep; omega <= 1 stays bounded
+            let omega_value: f64 = args.get(4).and_then(|s| s.parse().ok()).unwrap_or(1.5);
+            println!("omega: {}", omega_value);
+            let omega = IntervalField::point(DoubleField::new(omega_value));
+            let mut g = vec![vec![omega.zero(); n]; m];
+            let mut g_double = vec![vec![DoubleField::new(0.0); n]; m];
+            for j in 0..n {
+                g[0][j] = IntervalField::point(DoubleField::new(100.0));
+                g_double[0][j] = DoubleField::new(100.0);
+            }
+
+            // run in chunks to show how the enclosure widens with the iteration count
+            let chunk = 100;
+            let mut done = 0;
+            while done < num_iterations {
+                execute(omega.clone(), &mut g, chunk);
+                execute(DoubleField::new(omega_value), &mut g_double, chunk);
+                done += chunk;
+                let mut max_width: f64 = 0.0;
+                let mut outside = 0;
+                for i in 0..m {
+                    for j in 0..n {
+                        max_width = max_width.max(g[i][j].width());
+                        if !g[i][j].contains(g_double[i][j].d) {
+                            outside += 1;
+                        }
+                    }
+                }
+                println!("Iteration {}: max enclosure width {:e}, DoubleField values outside {}", done, max_width, outside);
+                if !max_width.is_finite() {
+                    break;
+                }
+            }
+            println!("g[1][n/2] enclosure: {}", g[1][n / 2]);
+        }
+        else if field_type == 4 {
+            println!("Rust generic dual doublefield SOR");
+            println!("Grid size: {}x{}", m, n);
+            println!("Number of iterations: {}", num_iterations);
+            let omega_value: f64 = args.get(4).and_then(|s| s.parse().ok()).unwrap_or(1.5);
+            println!("omega: {}", omega_value);
+            let omega = DualField::variable(DoubleField::new(omega_value));
+            let mut g = vec![vec![omega.zero(); n]; m];
+            for j in 0..n {
+                g[0][j] = DualField::constant(DoubleField::new(100.0));
+            }
+
+            // central difference of two plain DoubleField runs as a check on the dual derivative
+            let h = 1e-6;
+            let mut g_plus = vec![vec![DoubleField::new(0.0); n]; m];
+            let mut g_minus = vec![vec![DoubleField::new(0.0); n]; m];
+            for j in 0..n {
+                g_plus[0][j] = DoubleField::new(100.0);
+                g_minus[0][j] = DoubleField::new(100.0);
+            }
+
+            // dg/domega goes to zero as the iteration converges, the fixed point does not depend on omega
+            let (ci, cj) = (m / 2, n / 2);
+            let mut done = 0;
+            for &target in &[10, 100, 1000, num_iterations] {
+                execute(omega.clone(), &mut g, target - done);
+                execute(DoubleField::new(omega_value + h), &mut g_plus, target - done);
+                execute(DoubleField::new(omega_value - h), &mut g_minus, target - done);
+                done = target;
+                let finite_difference = (g_plus[ci][cj].d - g_minus[ci][cj].d) / (2.0 * h);
+                println!("Iteration {}: g[{}][{}] = {}, d/domega = {:e} (central difference {:e})",
+                    done, ci, cj, g[ci][cj].value, g[ci][cj].derivative.d, finite_difference);
+            }
+        }
+        else if field_type == 5 {
+            println!("Rust generic SOR operation counts");
+            println!("Grid size: {}x{}", m, n);
+            println!("Number of iterations: {}", num_iterations);
+            set_modulus(7727);
+            // 1.5 as 3/2 mod p
+            op_count_report("IntModP", IntModP::new(3).d(&IntModP::new(2)), IntModP::new(100), n, num_iterations);
+            op_count_report("DoubleField", DoubleField::new(1.5), DoubleField::new(100.0), n, num_iterations);
+            op_count_report("ComplexField<DoubleField>",
+                ComplexField::new(DoubleField::new(1.5), DoubleField::new(0.0)),
+                ComplexField::new(DoubleField::new(100.0), DoubleField::new(1.0)), n, num_iterations);
+        }
+        else if field_type == 6 {
+            println!("Rust generic SOR method API against operators");
+            println!("Grid size: {}x{}", m, n);
+            println!("Number of iterations: {}", num_iterations);
+            set_modulus(7727);
+            ops_report("IntModP", IntModP::new(3).d(&IntModP::new(2)), IntModP::new(100), n, num_iterations);
+            ops_report("SingleField", SingleField::new(1.5), SingleField::new(100.0), n, num_iterations);
+            ops_report("DoubleField", DoubleField::new(1.5), DoubleField::new(100.0), n, num_iterations);
+            ops_report("ComplexField<DoubleField>",
+                ComplexField::new(DoubleField::new(1.5), DoubleField::new(0.0)),
+                ComplexField::new(DoubleField::new(100.0), DoubleField::new(1.0)), n, num_iterations);
+        }
         /* else {
             println!("Rust generic IntModP SOR");
             println!("Grid size: {}x{}", m, n);
@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::Hash;
+use crate::generic::i_field::IField;
+use crate::generic::i_exponent::IExponent;
+use crate::generic::monomial_order::MonomialOrder;
+use crate::gen_grobner::{interreduce, GrobnerError, Polynomial, Term};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SignatureStats {
+    // S-pairs formed from two basis elements, input polynomials are not counted
+    pub created: usize,
+    // both halves with the same signature, the S-polynomial is not regular
+    pub singular: usize,
+    // signature divisible by the leading monomial of an element of lower index (the F5
+    // criterion) or by the signature of an earlier reduction to zero
+    pub syzygy: usize,
+    // signature divisible by the signature of an element added after the pair's generator,
+    // or the same signature as the pair taken just before
+    pub rewritten: usize,
+    // polynomials reduced, input ones included, how many reduced to zero and how many were
+    // dropped as singular top reducible after reduction
+    pub reduced: usize,
+    pub zero: usize,
+    pub redundant: usize,
+}
+
+impl SignatureStats {
+    pub fn skipped(&self) -> usize {
+        self.singular + self.syzygy + self.rewritten
+    }
+}
+
+impl fmt::Display for SignatureStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pairs {} created, {} skipped (singular {}, syzygy {}, rewritten {}), {} reduced, {} to zero, {} redundant",
+            self.created, self.skipped(), self.singular, self.syzygy, self.rewritten, self.reduced, self.zero, self.redundant)
+    }
+}
+
+// the module monomial m e_index, compared position over term: index first, then m in the term
+// order. Input polynomial i has signature e_i
+#[derive(Clone, Debug)]
+struct Signature<E> {
+    monomial: E,
+    index: usize,
+}
+
+impl<E: IExponent> Signature<E> {
+    fn compare<O: MonomialOrder>(&self, other: &Signature<E>, order: &O) -> Ordering {
+        self.index.cmp(&other.index).then_with(|| order.compare(&self.monomial, &other.monomial))
+    }
+
+    fn divides(&self, other: &Signature<E>) -> bool {
+        self.index == other.index && other.monomial.can_reduce(&self.monomial)
+    }
+
+    fn times(&self, monomial: &E) -> Result<Signature<E>, GrobnerError> {
+        let monomial = self.monomial.checked_add(monomial).ok_or(GrobnerError::ExponentOverflow)?;
+        Ok(Signature { monomial, index: self.index })
+    }
+}
+
+// what to reduce next: multiplier times the generator element, less the other element of an
+// S-pair, or an input polynomial on its own
+struct Candidate<E> {
+    signature: Signature<E>,
+    generator: usize,
+    other: Option<usize>,
+}
+
+// a reduced basis and the counts of the signature based run that found it
+pub type SignatureBasis<C, E, O> = Result<(Vec<Polynomial<C, E, O>>, SignatureStats), GrobnerError>;
+
+// a signature based Buchberger (the SB algorithm of Roune and Stillman, close to F5 and GVW).
+// Every element carries the signature of the module element it came from, pairs are taken by
+// increasing signature and reduced by regular reductions only, those that keep the signature.
+// A pair is skipped without reduction when the signatures of its halves are equal, when a
+// syzygy is known at its signature, or when a newer element can be rewritten to it. Returns
+// the reduced basis in the order its elements were found and the counts
+pub fn signature_grobner_basis<C, E, O>(polynomials: Vec<Polynomial<C, E, O>>) -> SignatureBasis<C, E, O>
+where
+    C: IField + Clone + Hash + Eq + fmt::Display,
+    E: IExponent + Clone + Hash + Eq + fmt::Display,
+    O: MonomialOrder,
+{
+    let inputs: Vec<Polynomial<C, E, O>> = polynomials.into_iter().filter(|poly| !poly.terms.is_empty()).collect();
+    let Some(order) = inputs.first().map(|poly| poly.order.clone()) else {
+        return Ok((Vec::new(), SignatureStats::default()));
+    };
+    let mut stats = SignatureStats::default();
+    let mut basis: Vec<Polynomial<C, E, O>> = Vec::new();
+    let mut signatures: Vec<Signature<E>> = Vec::new();
+    let mut syzygies: Vec<Signature<E>> = Vec::new();
+    let mut candidates: Vec<Candidate<E>> = inputs.iter().enumerate().map(|(index, poly)| {
+        // the exponent of 1, which IExponent has no constructor for
+        let one = poly.terms[0].exponents.sub(&poly.terms[0].exponents);
+        Candidate { signature: Signature { monomial: one, index }, generator: index, other: None }
+    }).collect();
+    let mut previous: Option<Signature<E>> = None;
+
+    while let Some(next) = (0..candidates.len()).min_by(|&a, &b| candidates[a].signature.compare(&candidates[b].signature, &order)) {
+        let candidate = candidates.swap_remove(next);
+        let signature = candidate.signature;
+
+        let mut poly = match candidate.other {
+            None => inputs[candidate.generator].clone(),
+            Some(other) => {
+                if previous.as_ref().is_some_and(|p| p.compare(&signature, &order).is_eq())
+                    || (candidate.generator + 1..basis.len()).any(|k| signatures[k].divides(&signature)) {
+                    stats.rewritten += 1;
+                    continue;
+                }
+                // F5: the leading monomial of an element of lower index times e_index is the
+                // signature of a Koszul syzygy. Every such element is already in the basis,
+                // their signatures are all smaller
+                if syzygies.iter().any(|s| s.divides(&signature))
+                    || (0..basis.len()).any(|k| signatures[k].index < signature.index && signature.monomial.can_reduce(&basis[k].terms[0].exponents)) {
+                    stats.syzygy += 1;
+                    continue;
+                }
+                Polynomial::s_polynomial(&basis[candidate.generator], &basis[other])?
+            }
+        };
+        previous = Some(signature.clone());
+
+        stats.reduced += 1;
+        let mut singular = false;
+        let mut at = 0;
+        while at < poly.terms.len() {
+            let term = &poly.terms[at];
+            let mut reducer = None;
+            for k in 0..basis.len() {
+                if !term.exponents.can_reduce(&basis[k].terms[0].exponents) {
+                    continue;
+                }
+                let multiplier = term.exponents.sub(&basis[k].terms[0].exponents);
+                match signatures[k].times(&multiplier)?.compare(&signature, &order) {
+                    Ordering::Less => {
+                        reducer = Some((k, multiplier));
+                        break;
+                    }
+                    Ordering::Equal if at == 0 => singular = true,
+                    _ => {}
+                }
+            }
+            match reducer {
+                Some((k, multiplier)) => {
+                    // basis elements are monic
+                    let scaled = basis[k].multiply_by_term(&Term::from_exponents(term.coefficient.clone(), multiplier))?;
+                    poly = poly.subtract(&scaled);
+                    singular = false;
+                }
+                // a leading term only a multiple of the same signature reduces makes this an
+                // element the basis already has
+                None if singular => break,
+                None => at += 1,
+            }
+        }
+
+        if poly.terms.is_empty() {
+            stats.zero += 1;
+            syzygies.push(signature);
+            continue;
+        }
+        if singular {
+            stats.redundant += 1;
+            continue;
+        }
+        let poly = poly.make_monic()?;
+        let h = basis.len();
+        for k in 0..h {
+            stats.created += 1;
+            let lcm = poly.terms[0].lcm(&basis[k].terms[0]);
+            let mine = signature.times(&lcm.sub(&poly.terms[0].exponents))?;
+            let theirs = signatures[k].times(&lcm.sub(&basis[k].terms[0].exponents))?;
+            match mine.compare(&theirs, &order) {
+                Ordering::Equal => stats.singular += 1,
+                Ordering::Greater => candidates.push(Candidate { signature: mine, generator: h, other: Some(k) }),
+                Ordering::Less => candidates.push(Candidate { signature: theirs, generator: k, other: Some(h) }),
+            }
+        }
+        basis.push(poly);
+        signatures.push(signature);
+    }
+
+    Ok((interreduce(&basis, |_| true)?, stats))
+}
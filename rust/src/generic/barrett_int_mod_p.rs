@@ -0,0 +1,205 @@
+use std::fmt;
+use crate::generic::i_field::IField;
+use crate::generic::field_ops::impl_field_ops;
+use crate::generic::i_normed::INormed;
+use crate::generic::i_math::IMath;
+use crate::generic::i_primitive_roots::IPrimitiveRoots;
+use crate::generic::roots_of_unity::{roots_of_unity, powers, root_of_unity_mod};
+use std::sync::Arc;
+use crate::generic::i_copiable::ICopiable;
+use crate::generic::int_mod_p::{mod_inverse, primitive_root_mod, sqrt_mod};
+use std::hash::Hash;
+use std::cmp::Eq;
+
+// Prime field element reduced with Barrett's method: x mod P is computed from a precomputed
+// mu = floor(4^k / P) with a multiply and shifts instead of a `%`. P must be below 2^63
+#[derive(Debug)]
+pub struct BarrettIntModP<const P: u64> {
+    pub i: u64,
+}
+
+impl<const P: u64> BarrettIntModP<P> {
+    // bit length of P
+    const K: u32 = {
+        assert!(P > 2 && P < (1 << 63), "BarrettIntModP needs a modulus between 3 and 2^63");
+        64 - P.leading_zeros()
+    };
+    const MU: u128 = (1u128 << (2 * Self::K)) / P as u128;
+
+    pub fn new(i: u64) -> Self {
+        BarrettIntModP { i: i % P }
+    }
+
+    pub fn modulus() -> u64 {
+        P
+    }
+
+    // x mod P for x < P^2; (x >> (k-1)) and mu both have at most k+1 bits, so nothing overflows u128
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        let q = ((x >> (Self::K - 1)) * Self::MU) >> (Self::K + 1);
+        let mut r = x - q * P as u128;
+        while r >= P as u128 {
+            r -= P as u128;
+        }
+        r as u64
+    }
+}
+
+impl<const P: u64> IField for BarrettIntModP<P> {
+    fn a(&self, o: &Self) -> Self {
+        let r = self.i + o.i;
+        BarrettIntModP { i: if r >= P { r - P } else { r } }
+    }
+
+    fn ae(&mut self, o: &Self) {
+        self.i += o.i;
+        if self.i >= P {
+            self.i -= P;
+        }
+    }
+
+    fn s(&self, o: &Self) -> Self {
+        BarrettIntModP { i: if self.i >= o.i { self.i - o.i } else { self.i + P - o.i } }
+    }
+
+    fn se(&mut self, o: &Self) {
+        self.i = if self.i >= o.i { self.i - o.i } else { self.i + P - o.i };
+    }
+
+    fn m(&self, o: &Self) -> Self {
+        BarrettIntModP { i: Self::reduce(self.i as u128 * o.i as u128) }
+    }
+
+    fn me(&mut self, o: &Self) {
+        self.i = Self::reduce(self.i as u128 * o.i as u128);
+    }
+
+    fn d(&self, o: &Self) -> Self {
+        if o.i == 0 {
+            panic!("Division by zero in BarrettIntModP, {}", P);
+        }
+        BarrettIntModP { i: Self::reduce(self.i as u128 * mod_inverse(o.i, P) as u128) }
+    }
+
+    fn de(&mut self, o: &Self) {
+        if o.i == 0 {
+            panic!("Division by zero in BarrettIntModP, {}", P);
+        }
+        self.i = Self::reduce(self.i as u128 * mod_inverse(o.i, P) as u128);
+    }
+
+    fn coerce_to_f64(&self) -> f64 {
+        self.i as f64
+    }
+
+    fn coerce_from_int(&self, value: i32) -> Self {
+        BarrettIntModP::new((value as i64).rem_euclid(P as i64) as u64)
+    }
+
+    fn coerce(&self, value: f64) -> Self {
+        BarrettIntModP::new(value as u64)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.i == 0
+    }
+    fn is_one(&self) -> bool {
+        self.i == 1
+    }
+
+    fn zero(&self) -> Self {
+        BarrettIntModP { i: 0 }
+    }
+    fn one(&self) -> Self {
+        BarrettIntModP { i: 1 }
+    }
+    fn new_zero() -> Self {
+        BarrettIntModP { i: 0 }
+    }
+    fn new_one() -> Self {
+        BarrettIntModP { i: 1 }
+    }
+}
+
+impl_field_ops!([const P: u64] BarrettIntModP<P>);
+
+impl<const P: u64> ICopiable for BarrettIntModP<P> {
+    fn copy(&self) -> Self {
+        BarrettIntModP { i: self.i }
+    }
+}
+
+impl<const P: u64> Clone for BarrettIntModP<P> {
+    fn clone(&self) -> Self {
+        self.copy()
+    }
+}
+
+impl<const P: u64> INormed for BarrettIntModP<P> {
+    fn norm(&self) -> f64 {
+        self.i as f64
+    }
+}
+
+impl<const P: u64> IMath for BarrettIntModP<P> {
+    fn abs(&self) -> Self {
+        self.copy()
+    }
+
+    fn sqrt(&mut self) -> Self {
+        self.try_sqrt().unwrap_or_else(|| panic!("{} is not a square mod {}", self.i, P))
+    }
+
+    fn try_sqrt(&self) -> Option<Self> {
+        sqrt_mod(self.i, P).map(BarrettIntModP::new)
+    }
+}
+
+impl<const P: u64> fmt::Display for BarrettIntModP<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IntModP({})", self.i)
+    }
+}
+
+impl<const P: u64> IPrimitiveRoots<BarrettIntModP<P>> for BarrettIntModP<P> {
+    fn primitive_root(&self, _n: u64) -> Self {
+        Self::new(primitive_root_mod(P))
+    }
+
+    fn pow(&self, exp: u64) -> Self {
+        let mut result = self.one();
+        let mut base = self.copy();
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result.me(&base);
+            }
+            base = base.m(&base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<Self> {
+        self.roots_of_unity(n, direction).to_vec()
+    }
+
+    fn roots_of_unity(&self, n: u32, direction: i32) -> Arc<Vec<Self>> {
+        roots_of_unity(P, n, direction, || powers(&Self::new(root_of_unity_mod(P, n, direction)), n))
+    }
+}
+
+impl<const P: u64> Hash for BarrettIntModP<P> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.i.hash(state);
+    }
+}
+
+impl<const P: u64> PartialEq for BarrettIntModP<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.i == other.i
+    }
+}
+
+impl<const P: u64> Eq for BarrettIntModP<P> {}
@@ -2,116 +2,338 @@ use std::fmt;
 use crate::generic::i_exponent::IExponent;
 use std::hash::{Hash, Hasher};
 
-#[derive(Clone, PartialEq, Eq, Debug)]
-pub struct BitPackedExponent {
-    pub exponents: u64
+// VARS exponents of BITS bits each, packed into WORDS u64s with the total degree in the top 16
+// bits of the first word. The first variable sits just below the degree and the fields run
+// towards the low bits, continuing at the top of the next word, so comparing the words as
+// integers is a degree-then-lex comparison and add/sub are plain word additions.
+// A field never straddles two words. WORDS has to be exactly what the layout needs, which is
+// checked when the first exponent is built: 6 x 8 and 12 x 4 fit in one word, 7 x 8 needs two
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BitPackedExponent<const VARS: usize, const BITS: usize, const WORDS: usize = 1> {
+    pub words: [u64; WORDS]
 }
-impl BitPackedExponent {
-    pub fn new(exponents: u64) -> Self {
-        BitPackedExponent { exponents }
+
+const DEGREE_BITS: usize = 16;
+
+// words needed for `vars` fields of `bits` bits after the degree
+pub const fn words_for(vars: usize, bits: usize) -> usize {
+    let first = (64 - DEGREE_BITS) / bits;
+    if vars <= first { 1 } else { 1 + (vars - first).div_ceil(64 / bits) }
+}
+
+impl<const VARS: usize, const BITS: usize, const WORDS: usize> BitPackedExponent<VARS, BITS, WORDS> {
+    const LAYOUT: () = assert!(
+        BITS > 0 && BITS <= DEGREE_BITS && WORDS == words_for(VARS, BITS),
+        "WORDS does not match words_for(VARS, BITS)"
+    );
+    const MASK: u64 = (1 << BITS) - 1;
+    const EXPONENT_MASK: u64 = (1 << (64 - DEGREE_BITS)) - 1;
+    // the top bit of every field, per word
+    const TOPS: [u64; WORDS] = {
+        let mut tops = [0u64; WORDS];
+        let mut i = 0;
+        while i < VARS {
+            let (word, shift) = Self::position(i);
+            tops[word] |= 1 << (shift + BITS - 1);
+            i += 1;
+        }
+        tops
+    };
+    // every field bit, per word, so the degree is masked off the first word
+    const FIELDS: [u64; WORDS] = {
+        let mut fields = [0u64; WORDS];
+        let mut i = 0;
+        while i < VARS {
+            let (word, shift) = Self::position(i);
+            fields[word] |= Self::MASK << shift;
+            i += 1;
+        }
+        fields
+    };
+    // shift of the lowest field, per word
+    const LOWEST: [usize; WORDS] = {
+        let mut lowest = [64usize; WORDS];
+        let mut i = 0;
+        while i < VARS {
+            let (word, shift) = Self::position(i);
+            if shift < lowest[word] {
+                lowest[word] = shift;
+            }
+            i += 1;
+        }
+        lowest
+    };
+    // alternating blocks of BITS << step bits for folding field sums, BITS = 1 takes six folds
+    const BLOCKS: [u64; 6] = {
+        let mut blocks = [0u64; 6];
+        let mut step = 0;
+        while step < 6 {
+            let width = BITS << step;
+            let mut bit = 0;
+            while bit < 64 {
+                if (bit / width) & 1 == 0 {
+                    blocks[step] |= 1 << bit;
+                }
+                bit += 1;
+            }
+            step += 1;
+        }
+        blocks
+    };
+
+    pub fn new(words: [u64; WORDS]) -> Self {
+        let () = Self::LAYOUT;
+        BitPackedExponent { words }
+    }
+
+    // word index and shift of variable i
+    #[inline(always)]
+    const fn position(i: usize) -> (usize, usize) {
+        let first = (64 - DEGREE_BITS) / BITS;
+        if i < first {
+            (0, 64 - DEGREE_BITS - BITS * (i + 1))
+        } else {
+            let j = i - first;
+            let per_word = 64 / BITS;
+            (1 + j / per_word, 64 - BITS * (j % per_word + 1))
+        }
+    }
+
+    #[inline(always)]
+    pub fn get(&self, i: usize) -> u32 {
+        let (word, shift) = Self::position(i);
+        ((self.words[word] >> shift) & Self::MASK) as u32
+    }
+
+    pub fn from_vec(exponents: [u32; VARS]) -> Self {
+        Self::from_slice(&exponents)
     }
 
-    pub fn from_vec(exponents: [u8; 6]) -> Self {
-        let mut packed: u64 = 0;
-        
-        // Pack the exponents with the first exponent at the left (bits 47..40)
-        // Layout: [63..48]=degree, [47..40]=e0, [39..32]=e1, ... [7..0]=e5
+    // missing trailing variables are zero
+    pub fn from_slice(exponents: &[u32]) -> Self {
+        assert!(exponents.len() <= VARS, "{} exponents for {} variables", exponents.len(), VARS);
+        let mut words = [0u64; WORDS];
         for (i, &exp) in exponents.iter().enumerate() {
-            let shift = 40_u32.saturating_sub((8 * i) as u32);
-            packed |= (exp as u64) << shift;
+            assert!(exp as u64 <= Self::MASK, "exponent {} does not fit {} bits", exp, BITS);
+            let (word, shift) = Self::position(i);
+            words[word] |= (exp as u64) << shift;
         }
+        let degree: u64 = exponents.iter().map(|&e| e as u64).sum();
+        assert!(degree < 1 << DEGREE_BITS, "degree {} does not fit {} bits", degree, DEGREE_BITS);
+        words[0] |= degree << (64 - DEGREE_BITS);
+        Self::new(words)
+    }
 
-        // Compute the total degree (sum of all exponents)
-        let degree: u16 = exponents.iter().map(|&e| e as u16).sum();
+    pub fn to_vec(&self) -> Vec<u32> {
+        (0..VARS).map(|i| self.get(i)).collect()
+    }
 
-        // Add the degree to the top 16 bits of the u64
-        packed |= (degree as u64) << 48;
+    // the top bits of the fields of word w where a is below b. Setting the top bit of every field
+    // of a and clearing it in b keeps the borrows of a - b inside their fields, a field then
+    // borrows out when b's top bit is larger, or the top bits are equal and the low bits borrowed
+    #[inline(always)]
+    fn below(a: u64, b: u64, w: usize) -> u64 {
+        let (a, b, top) = (a & Self::FIELDS[w], b & Self::FIELDS[w], Self::TOPS[w]);
+        let low = (a | top) - (b & !top);
+        top & ((!a & b) | (!(a ^ b) & !low))
+    }
 
-        BitPackedExponent { exponents: packed }
+    // per field max of word w, the borrow bits of a - b widened into field masks select b
+    #[inline(always)]
+    fn max_word(a: u64, b: u64, w: usize) -> u64 {
+        let lowest = Self::below(a, b, w) >> (BITS - 1);
+        let take_b = (lowest << BITS).wrapping_sub(lowest);
+        (a & !take_b | b & take_b) & Self::FIELDS[w]
     }
-}
 
-impl Hash for BitPackedExponent {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.exponents.hash(state);
+    // sum of the fields of word w, folded pairwise into blocks twice as wide until one is left
+    #[inline(always)]
+    fn field_sum(x: u64, w: usize) -> u64 {
+        let mut x = (x & Self::FIELDS[w]) >> Self::LOWEST[w];
+        let mut step = 0;
+        while (BITS << step) < 64 - Self::LOWEST[w] {
+            x = (x & Self::BLOCKS[step]) + ((x >> (BITS << step)) & Self::BLOCKS[step]);
+            step += 1;
+        }
+        x
     }
-}
 
-impl IExponent for BitPackedExponent {
-    fn add(&self, o: &Self) -> Self {
-        BitPackedExponent { exponents: self.exponents + o.exponents }
+    fn with_degree(mut words: [u64; WORDS]) -> Self {
+        let degree: u64 = (0..WORDS).map(|w| Self::field_sum(words[w], w)).sum();
+        words[0] |= degree << (64 - DEGREE_BITS);
+        BitPackedExponent { words }
     }
 
-    fn sub(&self, o: &Self) -> Self {
-        BitPackedExponent { exponents: self.exponents - o.exponents }
+    pub fn lcm_swar(&self, other: &Self) -> Self {
+        let mut words = [0u64; WORDS];
+        for (w, word) in words.iter_mut().enumerate() {
+            *word = Self::max_word(self.words[w], other.words[w], w);
+        }
+        Self::with_degree(words)
     }
 
-    fn lcm(&self, other: &Self) -> Self {
-        let self_exponents = self.exponents & 0x0000_FFFF_FFFF_FFFF;
-        let other_exponents = other.exponents & 0x0000_FFFF_FFFF_FFFF;
+    pub fn can_reduce_swar(&self, divisor: &Self) -> bool {
+        (0..WORDS).fold(0, |below, w| below | Self::below(self.words[w], divisor.words[w], w)) == 0
+    }
+
+    // one field at a time, kept as the reference for fuzzing and benchmarks
+    pub fn lcm_fieldwise(&self, other: &Self) -> Self {
+        let mut words = [0u64; WORDS];
+        let mut degree: u64 = 0;
+        for i in 0..VARS {
+            let (word, shift) = Self::position(i);
+            let lcm_exp = ((self.words[word] >> shift) & Self::MASK).max((other.words[word] >> shift) & Self::MASK);
+            words[word] |= lcm_exp << shift;
+            degree += lcm_exp;
+        }
+        words[0] |= degree << (64 - DEGREE_BITS);
+        BitPackedExponent { words }
+    }
+
+    pub fn can_reduce_fieldwise(&self, divisor: &Self) -> bool {
+        (0..VARS).all(|i| {
+            let (word, shift) = Self::position(i);
+            ((self.words[word] >> shift) & Self::MASK) >= ((divisor.words[word] >> shift) & Self::MASK)
+        })
+    }
+}
 
-        let mut lcm_exponents: u64 = 0;
-        let mut degree: u16 = 0;
+// byte fields two words at a time with the SSE2 unsigned byte max, which every x86_64 has.
+// Only the 8 bit layouts with more than one word take this path, an odd last word goes through
+// the SWAR code
+#[cfg(target_arch = "x86_64")]
+impl<const VARS: usize, const BITS: usize, const WORDS: usize> BitPackedExponent<VARS, BITS, WORDS> {
+    const SSE2: bool = BITS == 8 && WORDS > 1;
 
-        for i in (0..48).step_by(8) {
-            let self_exp = (self_exponents >> i) & 0xFF;
-            let other_exp = (other_exponents >> i) & 0xFF;
+    pub fn lcm_sse2(&self, other: &Self) -> Self {
+        use std::arch::x86_64::{__m128i, _mm_loadu_si128, _mm_max_epu8, _mm_storeu_si128};
+        assert!(Self::SSE2, "lcm_sse2 needs 8 bit fields in more than one word");
+        let mut words = [0u64; WORDS];
+        let (mut a, mut b) = (self.words, other.words);
+        for w in 0..WORDS {
+            a[w] &= Self::FIELDS[w];
+            b[w] &= Self::FIELDS[w];
+        }
+        for w in (0..WORDS - 1).step_by(2) {
+            // in bounds: w + 1 < WORDS, and loadu/storeu have no alignment requirement
+            unsafe {
+                let x = _mm_loadu_si128(a.as_ptr().add(w) as *const __m128i);
+                let y = _mm_loadu_si128(b.as_ptr().add(w) as *const __m128i);
+                _mm_storeu_si128(words.as_mut_ptr().add(w) as *mut __m128i, _mm_max_epu8(x, y));
+            }
+        }
+        if WORDS & 1 == 1 {
+            words[WORDS - 1] = Self::max_word(a[WORDS - 1], b[WORDS - 1], WORDS - 1);
+        }
+        Self::with_degree(words)
+    }
+
+    // a >= b in every byte when max(a, b) == a
+    pub fn can_reduce_sse2(&self, divisor: &Self) -> bool {
+        use std::arch::x86_64::{__m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_max_epu8, _mm_movemask_epi8};
+        assert!(Self::SSE2, "can_reduce_sse2 needs 8 bit fields in more than one word");
+        let (mut a, mut b) = (self.words, divisor.words);
+        for w in 0..WORDS {
+            a[w] &= Self::FIELDS[w];
+            b[w] &= Self::FIELDS[w];
+        }
+        for w in (0..WORDS - 1).step_by(2) {
+            let all_ge = unsafe {
+                let x = _mm_loadu_si128(a.as_ptr().add(w) as *const __m128i);
+                let y = _mm_loadu_si128(b.as_ptr().add(w) as *const __m128i);
+                _mm_movemask_epi8(_mm_cmpeq_epi8(_mm_max_epu8(x, y), x)) == 0xFFFF
+            };
+            if !all_ge {
+                return false;
+            }
+        }
+        WORDS & 1 == 0 || Self::below(a[WORDS - 1], b[WORDS - 1], WORDS - 1) == 0
+    }
+}
 
-            let lcm_exp = self_exp.max(other_exp);
+impl<const VARS: usize, const BITS: usize, const WORDS: usize> Hash for BitPackedExponent<VARS, BITS, WORDS> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.words.hash(state);
+    }
+}
+
+impl<const VARS: usize, const BITS: usize, const WORDS: usize> IExponent for BitPackedExponent<VARS, BITS, WORDS> {
+    fn add(&self, o: &Self) -> Self {
+        let mut words = self.words;
+        for (w, x) in words.iter_mut().zip(&o.words) {
+            *w += x;
+        }
+        BitPackedExponent { words }
+    }
 
-            lcm_exponents |= lcm_exp << i;
-            degree += lcm_exp as u16; // Accumulate the degree
+    // SWAR add with the field top bits held back, so no carry crosses a field boundary. A field
+    // overflows when its top bits carry out: both set, or one set and the low bits carried in.
+    // The degree sits in the top of the first word and overflows out of the u64
+    fn checked_add(&self, o: &Self) -> Option<Self> {
+        let mut words = [0u64; WORDS];
+        for (w, word) in words.iter_mut().enumerate() {
+            let (a, b, top) = (self.words[w], o.words[w], Self::TOPS[w]);
+            let (low, degree_carry) = (a & !top).overflowing_add(b & !top);
+            if degree_carry || top & ((a & b) | ((a ^ b) & low)) != 0 {
+                return None;
+            }
+            *word = low ^ ((a ^ b) & top);
         }
+        Some(BitPackedExponent { words })
+    }
 
-        // Set the degree in the top 16 bits
-        lcm_exponents |= (degree as u64) << 48;
+    fn sub(&self, o: &Self) -> Self {
+        let mut words = self.words;
+        for (w, x) in words.iter_mut().zip(&o.words) {
+            *w -= x;
+        }
+        BitPackedExponent { words }
+    }
 
-        BitPackedExponent { exponents: lcm_exponents }
+    fn lcm(&self, other: &Self) -> Self {
+        #[cfg(target_arch = "x86_64")]
+        if Self::SSE2 {
+            return self.lcm_sse2(other);
+        }
+        self.lcm_swar(other)
     }
 
     fn degree(&self) -> u32 {
-        ((self.exponents >> 48) & 0xFFFF) as u32
+        (self.words[0] >> (64 - DEGREE_BITS)) as u32
     }
 
+    // the degree bits masked off the first word, the rest compare as integers
     fn lex_compare(&self, other: &Self) -> std::cmp::Ordering {
-        for i in (0..48).step_by(8).rev() {
-            let self_exp = self.exponents & 0x0000_FFFF_FFFF_FFFF;
-            let other_exp = other.exponents & 0x0000_FFFF_FFFF_FFFF;
-
-            if self_exp < other_exp {
-                return std::cmp::Ordering::Less;
-            } else if self_exp > other_exp {
-                return std::cmp::Ordering::Greater;
-            }
-        }
-        std::cmp::Ordering::Equal
+        (self.words[0] & Self::EXPONENT_MASK).cmp(&(other.words[0] & Self::EXPONENT_MASK))
+            .then_with(|| self.words[1..].cmp(&other.words[1..]))
     }
 
     fn can_reduce(&self, divisor: &Self) -> bool {
-        for i in (0..48).step_by(8) {
-            let self_exp = (self.exponents >> i) & 0xFF;
-            let divisor_exp = (divisor.exponents >> i) & 0xFF;
-
-            if self_exp < divisor_exp {
-                return false;
-            }
+        #[cfg(target_arch = "x86_64")]
+        if Self::SSE2 {
+            return self.can_reduce_sse2(divisor);
         }
-        true
+        self.can_reduce_swar(divisor)
+    }
+
+    fn exponent(&self, var: usize) -> u32 {
+        if var < VARS { self.get(var) } else { 0 }
+    }
+
+    fn vars(&self) -> usize {
+        VARS
     }
 
 }
 
 
-impl fmt::Display for BitPackedExponent {
+impl<const VARS: usize, const BITS: usize, const WORDS: usize> fmt::Display for BitPackedExponent<VARS, BITS, WORDS> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Extract degree (top 16 bits)
-        let degree = (self.exponents >> 48) & 0xFFFF;
-        write!(f, "Degree: {:04X}, Exponents (hex): ", degree)?;
-        // Print each 8-bit exponent in hex, left to right (e0..e5)
-        for i in (0..48).step_by(8).rev() {
-            let exp = (self.exponents >> i) & 0xFF;
-            write!(f, "{:02X} ", exp)?;
+        write!(f, "Degree: {:04X}, Exponents (hex): ", self.degree())?;
+        // left to right (e0..e{VARS-1})
+        for i in 0..VARS {
+            write!(f, "{:0width$X} ", self.get(i), width = BITS.div_ceil(4))?;
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -1,12 +1,17 @@
 use core::fmt;
 use std::f64::consts::PI;
 use crate::generic::i_field::IField;
-use crate::generic::i_ordered::IOrdered;
+use crate::generic::field_error::FieldError;
+use crate::generic::field_ops::{impl_field_ops, impl_num_traits};
+use crate::generic::i_normed::INormed;
 use crate::generic::i_math::IMath;
 use crate::generic::single_field::SingleField;
 use crate::generic::double_field::DoubleField;
-use crate::generic::int_mod_p::IntModP;
+use crate::generic::double_double_field::DoubleDoubleField;
+use crate::generic::int_mod_p::{IntModP, get_modulus};
 use crate::generic::i_primitive_roots::IPrimitiveRoots;
+use crate::generic::roots_of_unity::roots_of_unity;
+use std::sync::Arc;
 use crate::generic::i_copiable::ICopiable;
 use std::hash::Hash;
 use std::cmp::Eq;
@@ -73,8 +78,23 @@ impl <T: IField> IField for ComplexField<T> {
         self.im = temp_im;
     }
 
+    // c + di != 0 can still have c^2 + d^2 = 0, over GF(p) with p = 1 mod 4 or by underflow,
+    // so a zero norm is reported as NotInvertible rather than DivisionByZero
+    fn checked_d(&self, o: &ComplexField<T>) -> Result<ComplexField<T>, FieldError> {
+        if o.is_zero() {
+            return Err(FieldError::DivisionByZero);
+        }
+        let denom = o.re.m(&o.re).a(&o.im.m(&o.im));
+        let not_invertible = |_| FieldError::NotInvertible;
+        Ok(ComplexField::new(
+            self.re.m(&o.re).a(&self.im.m(&o.im)).checked_d(&denom).map_err(not_invertible)?,
+            self.im.m(&o.re).s(&self.re.m(&o.im)).checked_d(&denom).map_err(not_invertible)?,
+        ))
+    }
+
+    // the real part, the inverse of coerce. The modulus is INormed::norm
     fn coerce_to_f64(&self) -> f64 {
-        (self.re.coerce_to_f64().powi(2) + self.im.coerce_to_f64().powi(2)).sqrt()
+        self.re.coerce_to_f64()
     }
 
     fn coerce_from_int(&self, value: i32) -> Self {
@@ -97,80 +117,122 @@ impl <T: IField> IField for ComplexField<T> {
     fn one(&self) -> ComplexField<T> {
         ComplexField::new(T::one(&self.re), T::zero(&self.im))
     }
+    fn new_zero() -> ComplexField<T> {
+        ComplexField::new(T::new_zero(), T::new_zero())
+    }
+    fn new_one() -> ComplexField<T> {
+        ComplexField::new(T::new_one(), T::new_zero())
+    }
+}
+
+impl_field_ops!([T: IField] ComplexField<T>);
+
+impl_num_traits!([T: IField] ComplexField<T>);
+
+// only the real part is parsed, the imaginary part is zero
+impl<T: IField + PartialEq + num_traits::Num> num_traits::Num for ComplexField<T> {
+    type FromStrRadixErr = T::FromStrRadixErr;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<ComplexField<T>, T::FromStrRadixErr> {
+        Ok(ComplexField::new(<T as num_traits::Num>::from_str_radix(s, radix)?, T::new_zero()))
+    }
 }
 
-impl<T: IField + IOrdered + ICopiable> ICopiable for ComplexField<T> {
+impl<T: IField + ICopiable> ICopiable for ComplexField<T> {
     fn copy(&self) -> Self {
         ComplexField::new(self.re.copy(), self.im.copy())
     }
 }
 
-impl <T: IField + IOrdered + ICopiable> Clone for ComplexField<T> {
+impl <T: IField + ICopiable> Clone for ComplexField<T> {
     fn clone(&self) -> Self {
         self.copy()
     }
 }
 
-impl<T: IField + IOrdered> IOrdered for ComplexField<T> {
-    fn lt(&self, o: &ComplexField<T>) -> bool {
-        self.re.lt(&o.re) || (self.re.e(&o.re) && self.im.lt(&o.im))
-    }
-
-    fn le(&self, o: &ComplexField<T>) -> bool {
-        self.re.lt(&o.re) || (self.re.e(&o.re) && (self.im.lt(&o.im) || self.im.e(&o.im)))
-    }
-
-    fn gt(&self, o: &ComplexField<T>) -> bool {
-        self.re.gt(&o.re) || (self.re.e(&o.re) && self.im.gt(&o.im))
-    }
-
-    fn ge(&self, o: &ComplexField<T>) -> bool {
-        self.re.gt(&o.re) || (self.re.e(&o.re) && (self.im.gt(&o.im) || self.im.e(&o.im)))
-    }
-
-    fn e(&self, o: &ComplexField<T>) -> bool {
-        self.re.e(&o.re) && self.im.e(&o.im)
+// the modulus. Complex numbers have no ordering compatible with the field, so there is no IOrdered
+impl<T: INormed> INormed for ComplexField<T> {
+    fn norm(&self) -> f64 {
+        self.re.norm().hypot(self.im.norm())
     }
 }
 
-impl<T: IField + IOrdered + fmt::Display> fmt::Display for ComplexField<T> {
+impl<T: IField + fmt::Display> fmt::Display for ComplexField<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.im.is_zero() {
             write!(f, "{}", self.re)
         } else if self.re.is_zero() {
             write!(f, "{}i", self.im)
         } else {
-            write!(
-                f,
-                "{}{}{}i",
-                self.re,
-                if self.im.lt(&T::zero(&self.im)) { "" } else { "+" },
-                self.im
-            )
+            // the sign comes from the printed imaginary part, so parts without an ordering work too
+            let im = self.im.to_string();
+            write!(f, "{}{}{}i", self.re, if im.starts_with('-') { "" } else { "+" }, im)
         }
     }
 }
 
+// x >= 0 by abs, so the parts need no IOrdered. Finite fields, whose abs is the identity, count
+// as non-negative and take the first branch of sqrt, which holds whenever its roots exist
+fn non_negative<T: IField + IMath>(x: &T) -> bool {
+    x.abs().s(x).is_zero()
+}
+
 impl<T: IField + ICopiable + IMath> IMath for ComplexField<T> {
+    // the modulus |z| as a complex number with zero imaginary part
     fn abs(&self) -> ComplexField<T> {
-        let re = self.re.copy();
-        let im = self.im.copy();
-        let mut temp = re.m(&re).a(&im.m(&im));
-        temp.sqrt();
-        temp.abs();
-        let zero = temp.zero();
-        ComplexField::new(temp, zero)
+        let mut norm_sq = self.re.m(&self.re).a(&self.im.m(&self.im));
+        ComplexField::new(norm_sq.sqrt(), self.re.zero())
     }
 
+    // principal root, Re >= 0. w = sqrt((|a| + |z|) / 2) and the other part b / 2w avoid the
+    // cancellation of the textbook formula for a < 0
     fn sqrt(&mut self) -> ComplexField<T> {
-        // Square root of a complex number is not straightforward and is not implemented here.
-        panic!("Square root not implemented for ComplexField");
+        if self.is_zero() {
+            return self.zero();
+        }
+        let zero = self.re.zero();
+        let mut w_sq = self.re.abs().a(&self.abs().re).d(&self.re.coerce(2.0));
+        let w = w_sq.sqrt();
+        let other = self.im.d(&w.a(&w));
+        if non_negative(&self.re) {
+            ComplexField::new(w, other)
+        } else if !non_negative(&self.im) {
+            ComplexField::new(other.abs(), zero.s(&w))
+        } else {
+            ComplexField::new(other.abs(), w)
+        }
+    }
+
+    fn conj(&self) -> ComplexField<T> {
+        ComplexField::new(self.re.copy(), self.im.zero().s(&self.im))
+    }
+
+    // exp, ln and powf go through f64 and are coerced back, enough for SingleField and
+    // DoubleField parts but not more
+    fn exp(&self) -> ComplexField<T> {
+        let r = self.re.coerce_to_f64().exp();
+        let theta = self.im.coerce_to_f64();
+        ComplexField::new(self.re.coerce(r * theta.cos()), self.im.coerce(r * theta.sin()))
+    }
+
+    // principal branch, arg in (-pi, pi]
+    fn ln(&self) -> ComplexField<T> {
+        let (a, b) = (self.re.coerce_to_f64(), self.im.coerce_to_f64());
+        ComplexField::new(self.re.coerce(a.hypot(b).ln()), self.im.coerce(b.atan2(a)))
+    }
+
+    // z^e = exp(e ln z), 0^e = 0 except 0^0 = 1
+    fn powf(&self, exponent: &ComplexField<T>) -> ComplexField<T> {
+        if self.is_zero() {
+            return if exponent.is_zero() { self.one() } else { self.zero() };
+        }
+        exponent.m(&self.ln()).exp()
     }
 }
 
 impl<T> Hash for ComplexField<T>
 where
-    T: IField + IOrdered + Hash,
+    T: IField + Hash,
 {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.re.hash(state);
@@ -180,7 +242,7 @@ where
 
 impl<T> PartialEq for ComplexField<T>
 where
-    T: IField + IOrdered + PartialEq,
+    T: IField + PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
         self.re.eq(&other.re) && self.im.eq(&other.im)
@@ -189,7 +251,7 @@ where
 
 impl<T> Eq for ComplexField<T>
 where
-    T: IField + IOrdered + Eq,
+    T: IField + Eq,
 {}
 
 // Implement PrimitiveRoot for SingleField numbers
@@ -229,14 +291,21 @@ impl IPrimitiveRoots<ComplexField<SingleField>> for ComplexField<SingleField> {
     }
 
     fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<ComplexField<SingleField>> {
-        let mut roots = Vec::new();
-        for k in 0..n {
-            let angle = 2.0 * PI * k as f64 / n as f64 * direction as f64;
-            let real = SingleField::new(angle.cos() as f32);
-            let imag = SingleField::new(angle.sin() as f32);
-            roots.push(ComplexField::new(real, imag));
-        }
-        roots
+        self.roots_of_unity(n, direction).to_vec()
+    }
+
+    // cos and sin of every angle rather than a running product, whose rounding error grows with k
+    fn roots_of_unity(&self, n: u32, direction: i32) -> Arc<Vec<ComplexField<SingleField>>> {
+        roots_of_unity(0, n, direction, || {
+            let mut roots = Vec::with_capacity(n as usize);
+            for k in 0..n {
+                let angle = 2.0 * PI * k as f64 / n as f64 * direction as f64;
+                let real = SingleField::new(angle.cos() as f32);
+                let imag = SingleField::new(angle.sin() as f32);
+                roots.push(ComplexField::new(real, imag));
+            }
+            roots
+        })
     }
 }
 
@@ -279,14 +348,21 @@ impl IPrimitiveRoots<ComplexField<DoubleField>> for ComplexField<DoubleField> {
     }
 
     fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<ComplexField<DoubleField>> {
-        let mut roots = Vec::new();
-        for k in 0..n {
-            let angle = 2.0 * PI * k as f64 / n as f64 * direction as f64;
-            let real = self.re.coerce(angle.cos());
-            let imag = self.im.coerce(angle.sin());
-            roots.push(ComplexField::new(real, imag));
-        }
-        roots
+        self.roots_of_unity(n, direction).to_vec()
+    }
+
+    // cos and sin of every angle rather than a running product, whose rounding error grows with k
+    fn roots_of_unity(&self, n: u32, direction: i32) -> Arc<Vec<ComplexField<DoubleField>>> {
+        roots_of_unity(0, n, direction, || {
+            let mut roots = Vec::with_capacity(n as usize);
+            for k in 0..n {
+                let angle = 2.0 * PI * k as f64 / n as f64 * direction as f64;
+                let real = self.re.coerce(angle.cos());
+                let imag = self.im.coerce(angle.sin());
+                roots.push(ComplexField::new(real, imag));
+            }
+            roots
+        })
     }
 }
 
@@ -305,31 +381,94 @@ impl IPrimitiveRoots<ComplexField<IntModP>> for ComplexField<IntModP> {
     }
 
     fn pow(&self, exponent: u64) -> Self {
-        if exponent == 0 {
-            return self.one(); // Any number to the power of 0 is 1
+        // square and multiply, Z_p[i] has no modulus or argument to go through a polar form with
+        let mut result = self.one();
+        let mut base = self.copy();
+        let mut e = exponent;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result.m(&base);
+            }
+            base = base.m(&base);
+            e >>= 1;
         }
+        result
+    }
 
-        // Convert to polar form
-        let r = self.abs(); // Modulus
-        let theta = self.im.coerce_to_f64().atan2(self.re.coerce_to_f64()); // Argument (angle)
+    fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<ComplexField<IntModP>> {
+        self.roots_of_unity(n, direction).to_vec()
+    }
 
-        // Compute new modulus and argument
-        let new_r = r.coerce_to_f64().powi(exponent as i32); // r^exponent
-        let new_theta = theta * exponent as f64; // theta * exponent
+    fn roots_of_unity(&self, n: u32, direction: i32) -> Arc<Vec<ComplexField<IntModP>>> {
+        roots_of_unity(get_modulus(), n, direction, || {
+            let mut roots = Vec::with_capacity(n as usize);
+            for k in 0..n {
+                let angle = 2.0 * PI * k as f64 / n as f64 * direction as f64;
+                let real = self.re.coerce(angle.cos());
+                let imag = self.im.coerce(angle.sin());
+                roots.push(ComplexField::new(real, imag));
+            }
+            roots
+        })
+    }
+}
 
-        // Convert back to rectangular form
-        let real = self.re.coerce(new_r * new_theta.cos());
-        let imag = self.im.coerce(new_r * new_theta.sin());
-        ComplexField::new(real, imag)
+
+// Double-double roots come from half angles instead of f64 cos/sin, which would cap the accuracy at 1e-16
+impl IPrimitiveRoots<ComplexField<DoubleDoubleField>> for ComplexField<DoubleDoubleField> {
+    fn primitive_root(&self, n: u64) -> Self {
+        if n == 0 || !n.is_power_of_two() {
+            panic!("n must be a power of two for DoubleDoubleField roots of unity");
+        }
+        let (c, s) = DoubleDoubleField::half_angle_roots(n.trailing_zeros()).pop().unwrap();
+        ComplexField::new(c, s)
     }
 
-    fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<ComplexField<IntModP>> {
-        let mut roots = Vec::new();
+    // square and multiply, so no precision is lost to a polar form round trip
+    fn pow(&self, exponent: u64) -> Self {
+        let mut result = self.one();
+        let mut base = self.copy();
+        let mut e = exponent;
+        while e > 0 {
+            if e & 1 == 1 {
+                result.me(&base);
+            }
+            base = base.m(&base);
+            e >>= 1;
+        }
+        result
+    }
+
+    fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<ComplexField<DoubleDoubleField>> {
+        self.roots_of_unity(n, direction).to_vec()
+    }
+
+    // w^k as the product of the half angle roots w^(2^b) for the bits b of k
+    fn roots_of_unity(&self, n: u32, direction: i32) -> Arc<Vec<ComplexField<DoubleDoubleField>>> {
+        roots_of_unity(0, n, direction, || self.half_angle_table(n, direction))
+    }
+}
+
+impl ComplexField<DoubleDoubleField> {
+    fn half_angle_table(&self, n: u32, direction: i32) -> Vec<ComplexField<DoubleDoubleField>> {
+        if n == 0 || !n.is_power_of_two() {
+            panic!("n must be a power of two for DoubleDoubleField roots of unity");
+        }
+        let levels = n.trailing_zeros();
+        let half_angles = DoubleDoubleField::half_angle_roots(levels);
+        let mut roots = Vec::with_capacity(n as usize);
         for k in 0..n {
-            let angle = 2.0 * PI * k as f64 / n as f64 * direction as f64;
-            let real = self.re.coerce(angle.cos());
-            let imag = self.im.coerce(angle.sin());
-            roots.push(ComplexField::new(real, imag));
+            let mut root = self.one();
+            for bit in 0..levels {
+                if k & (1 << bit) != 0 {
+                    let (c, s) = &half_angles[(levels - bit) as usize];
+                    root.me(&ComplexField::new(c.copy(), s.copy()));
+                }
+            }
+            if direction < 0 {
+                root.im = root.im.zero().s(&root.im);
+            }
+            roots.push(root);
         }
         roots
     }
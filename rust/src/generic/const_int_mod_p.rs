@@ -0,0 +1,175 @@
+use std::fmt;
+use crate::generic::i_field::IField;
+use crate::generic::field_ops::impl_field_ops;
+use crate::generic::i_normed::INormed;
+use crate::generic::i_math::IMath;
+use crate::generic::i_primitive_roots::IPrimitiveRoots;
+use crate::generic::roots_of_unity::{roots_of_unity, powers, root_of_unity_mod};
+use std::sync::Arc;
+use crate::generic::i_copiable::ICopiable;
+use crate::generic::int_mod_p::{mod_inverse, primitive_root_mod, mod_pow, mul_mod, sqrt_mod};
+use std::hash::Hash;
+use std::cmp::Eq;
+
+// Same arithmetic as IntModP, but the modulus is part of the type instead of the global MODULUS,
+// so several prime fields can be used side by side (CRT, multi-prime Grobner runs, threads)
+#[derive(Debug)]
+pub struct ConstIntModP<const P: u64> {
+    pub i: u64,
+}
+
+impl<const P: u64> ConstIntModP<P> {
+    pub fn new(i: u64) -> Self {
+        ConstIntModP { i: i % P }
+    }
+
+    pub fn modulus() -> u64 {
+        P
+    }
+}
+
+impl<const P: u64> IField for ConstIntModP<P> {
+    fn a(&self, o: &Self) -> Self {
+        ConstIntModP::new(self.i + o.i)
+    }
+
+    fn ae(&mut self, o: &Self) {
+        self.i = (self.i + o.i) % P;
+    }
+
+    fn s(&self, o: &Self) -> Self {
+        ConstIntModP::new(self.i + P - o.i)
+    }
+
+    fn se(&mut self, o: &Self) {
+        self.i = (self.i + P - o.i) % P;
+    }
+
+    fn m(&self, o: &Self) -> Self {
+        ConstIntModP { i: mul_mod(self.i, o.i, P) }
+    }
+
+    fn me(&mut self, o: &Self) {
+        self.i = mul_mod(self.i, o.i, P);
+    }
+
+    fn d(&self, o: &Self) -> Self {
+        if o.i == 0 {
+            panic!("Division by zero in ConstIntModP, {}", P);
+        }
+        let inv = mod_inverse(o.i, P);
+        ConstIntModP { i: mul_mod(self.i, inv, P) }
+    }
+
+    fn de(&mut self, o: &Self) {
+        if o.i == 0 {
+            panic!("Division by zero in ConstIntModP, {}", P);
+        }
+        let inv = mod_inverse(o.i, P);
+        self.i = mul_mod(self.i, inv, P);
+    }
+
+    fn coerce_to_f64(&self) -> f64 {
+        self.i as f64
+    }
+
+    fn coerce_from_int(&self, value: i32) -> Self {
+        ConstIntModP::new((value as i64).rem_euclid(P as i64) as u64)
+    }
+
+    fn coerce(&self, value: f64) -> Self {
+        ConstIntModP::new(value as u64)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.i == 0
+    }
+    fn is_one(&self) -> bool {
+        self.i == 1
+    }
+
+    fn zero(&self) -> Self {
+        ConstIntModP::new(0)
+    }
+    fn one(&self) -> Self {
+        ConstIntModP::new(1)
+    }
+    fn new_zero() -> Self {
+        ConstIntModP::new(0)
+    }
+    fn new_one() -> Self {
+        ConstIntModP::new(1)
+    }
+}
+
+impl_field_ops!([const P: u64] ConstIntModP<P>);
+
+impl<const P: u64> ICopiable for ConstIntModP<P> {
+    fn copy(&self) -> Self {
+        ConstIntModP { i: self.i }
+    }
+}
+
+impl<const P: u64> Clone for ConstIntModP<P> {
+    fn clone(&self) -> Self {
+        self.copy()
+    }
+}
+
+impl<const P: u64> INormed for ConstIntModP<P> {
+    fn norm(&self) -> f64 {
+        self.i as f64
+    }
+}
+
+impl<const P: u64> IMath for ConstIntModP<P> {
+    fn abs(&self) -> Self {
+        self.copy()
+    }
+
+    fn sqrt(&mut self) -> Self {
+        self.try_sqrt().unwrap_or_else(|| panic!("{} is not a square mod {}", self.i, P))
+    }
+
+    fn try_sqrt(&self) -> Option<Self> {
+        sqrt_mod(self.i, P).map(ConstIntModP::new)
+    }
+}
+
+impl<const P: u64> fmt::Display for ConstIntModP<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IntModP({})", self.i)
+    }
+}
+
+impl<const P: u64> IPrimitiveRoots<ConstIntModP<P>> for ConstIntModP<P> {
+    fn primitive_root(&self, _n: u64) -> Self {
+        Self::new(primitive_root_mod(P))
+    }
+
+    fn pow(&self, exp: u64) -> Self {
+        ConstIntModP::new(mod_pow(self.i, exp, P))
+    }
+
+    fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<Self> {
+        self.roots_of_unity(n, direction).to_vec()
+    }
+
+    fn roots_of_unity(&self, n: u32, direction: i32) -> Arc<Vec<Self>> {
+        roots_of_unity(P, n, direction, || powers(&Self::new(root_of_unity_mod(P, n, direction)), n))
+    }
+}
+
+impl<const P: u64> Hash for ConstIntModP<P> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.i.hash(state);
+    }
+}
+
+impl<const P: u64> PartialEq for ConstIntModP<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.i == other.i
+    }
+}
+
+impl<const P: u64> Eq for ConstIntModP<P> {}
@@ -0,0 +1,286 @@
+use std::fmt;
+use std::cell::Cell;
+use crate::generic::i_copiable::ICopiable;
+use crate::generic::i_field::IField;
+use crate::generic::field_error::FieldError;
+use crate::generic::field_ops::impl_field_ops;
+use crate::generic::i_math::IMath;
+use crate::generic::i_ordered::IOrdered;
+use crate::generic::i_normed::INormed;
+use crate::generic::i_primitive_roots::IPrimitiveRoots;
+use std::hash::Hash;
+use std::cmp::Eq;
+
+// Wraps any field and tallies every operation done through it in per-thread counters,
+// the replacement for the commented out FCOUNT increments in SingleField/DoubleField
+#[derive(Debug)]
+pub struct CountingField<T> {
+    pub inner: T,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpCounts {
+    pub adds: u64,
+    pub subs: u64,
+    pub muls: u64,
+    pub divs: u64,
+    pub comparisons: u64,
+    pub copies: u64,
+    // abs, sqrt, exp, ln, powf, conj, norm
+    pub math: u64,
+}
+
+impl OpCounts {
+    pub fn total(&self) -> u64 {
+        self.adds + self.subs + self.muls + self.divs + self.comparisons + self.copies + self.math
+    }
+}
+
+impl fmt::Display for OpCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "adds {}, subs {}, muls {}, divs {}, comparisons {}, copies {}, math {}",
+            self.adds, self.subs, self.muls, self.divs, self.comparisons, self.copies, self.math)
+    }
+}
+
+thread_local! {
+    static COUNTS: Cell<OpCounts> = Cell::new(OpCounts::default());
+}
+
+fn tally(update: impl FnOnce(&mut OpCounts)) {
+    COUNTS.with(|counts| {
+        let mut c = counts.get();
+        update(&mut c);
+        counts.set(c);
+    });
+}
+
+// counts of the current thread since the last reset
+pub fn op_counts() -> OpCounts {
+    COUNTS.with(|counts| counts.get())
+}
+
+pub fn reset_op_counts() {
+    COUNTS.with(|counts| counts.set(OpCounts::default()));
+}
+
+// runs f with fresh counters and returns its result with the operations it did on this thread
+pub fn count_ops<R>(f: impl FnOnce() -> R) -> (R, OpCounts) {
+    let saved = op_counts();
+    reset_op_counts();
+    let result = f();
+    let counts = op_counts();
+    COUNTS.with(|c| c.set(saved));
+    (result, counts)
+}
+
+impl<T> CountingField<T> {
+    pub fn new(inner: T) -> Self {
+        CountingField { inner }
+    }
+}
+
+impl<T: IField> IField for CountingField<T> {
+    fn a(&self, o: &CountingField<T>) -> CountingField<T> {
+        tally(|c| c.adds += 1);
+        CountingField::new(self.inner.a(&o.inner))
+    }
+
+    fn ae(&mut self, o: &CountingField<T>) {
+        tally(|c| c.adds += 1);
+        self.inner.ae(&o.inner);
+    }
+
+    fn s(&self, o: &CountingField<T>) -> CountingField<T> {
+        tally(|c| c.subs += 1);
+        CountingField::new(self.inner.s(&o.inner))
+    }
+
+    fn se(&mut self, o: &CountingField<T>) {
+        tally(|c| c.subs += 1);
+        self.inner.se(&o.inner);
+    }
+
+    fn m(&self, o: &CountingField<T>) -> CountingField<T> {
+        tally(|c| c.muls += 1);
+        CountingField::new(self.inner.m(&o.inner))
+    }
+
+    fn me(&mut self, o: &CountingField<T>) {
+        tally(|c| c.muls += 1);
+        self.inner.me(&o.inner);
+    }
+
+    fn d(&self, o: &CountingField<T>) -> CountingField<T> {
+        tally(|c| c.divs += 1);
+        CountingField::new(self.inner.d(&o.inner))
+    }
+
+    fn de(&mut self, o: &CountingField<T>) {
+        tally(|c| c.divs += 1);
+        self.inner.de(&o.inner);
+    }
+
+    // forwarded so the inner field's own checks apply
+    fn checked_d(&self, o: &CountingField<T>) -> Result<CountingField<T>, FieldError> {
+        tally(|c| c.divs += 1);
+        self.inner.checked_d(&o.inner).map(CountingField::new)
+    }
+
+    fn coerce_to_f64(&self) -> f64 {
+        self.inner.coerce_to_f64()
+    }
+
+    fn coerce_from_int(&self, value: i32) -> Self {
+        CountingField::new(self.inner.coerce_from_int(value))
+    }
+
+    fn coerce(&self, value: f64) -> CountingField<T> {
+        CountingField::new(self.inner.coerce(value))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.inner.is_zero()
+    }
+
+    fn is_one(&self) -> bool {
+        self.inner.is_one()
+    }
+
+    fn zero(&self) -> CountingField<T> {
+        CountingField::new(self.inner.zero())
+    }
+
+    fn one(&self) -> CountingField<T> {
+        CountingField::new(self.inner.one())
+    }
+
+    fn new_zero() -> CountingField<T> {
+        CountingField::new(T::new_zero())
+    }
+
+    fn new_one() -> CountingField<T> {
+        CountingField::new(T::new_one())
+    }
+}
+
+impl_field_ops!([T: IField] CountingField<T>);
+
+impl<T: ICopiable> ICopiable for CountingField<T> {
+    fn copy(&self) -> Self {
+        tally(|c| c.copies += 1);
+        CountingField::new(self.inner.copy())
+    }
+}
+
+impl<T: ICopiable> Clone for CountingField<T> {
+    fn clone(&self) -> Self {
+        self.copy()
+    }
+}
+
+impl<T: IMath + ICopiable> IMath for CountingField<T> {
+    fn abs(&self) -> CountingField<T> {
+        tally(|c| c.math += 1);
+        CountingField::new(self.inner.abs())
+    }
+
+    fn sqrt(&mut self) -> CountingField<T> {
+        tally(|c| c.math += 1);
+        CountingField::new(self.inner.sqrt())
+    }
+
+    fn try_sqrt(&self) -> Option<CountingField<T>> {
+        tally(|c| c.math += 1);
+        self.inner.try_sqrt().map(CountingField::new)
+    }
+
+    fn conj(&self) -> CountingField<T> {
+        tally(|c| c.math += 1);
+        CountingField::new(self.inner.conj())
+    }
+
+    fn exp(&self) -> CountingField<T> {
+        tally(|c| c.math += 1);
+        CountingField::new(self.inner.exp())
+    }
+
+    fn ln(&self) -> CountingField<T> {
+        tally(|c| c.math += 1);
+        CountingField::new(self.inner.ln())
+    }
+
+    fn powf(&self, exponent: &CountingField<T>) -> CountingField<T> {
+        tally(|c| c.math += 1);
+        CountingField::new(self.inner.powf(&exponent.inner))
+    }
+}
+
+impl<T: IOrdered> IOrdered for CountingField<T> {
+    fn lt(&self, o: &CountingField<T>) -> bool {
+        tally(|c| c.comparisons += 1);
+        self.inner.lt(&o.inner)
+    }
+
+    fn le(&self, o: &CountingField<T>) -> bool {
+        tally(|c| c.comparisons += 1);
+        self.inner.le(&o.inner)
+    }
+
+    fn gt(&self, o: &CountingField<T>) -> bool {
+        tally(|c| c.comparisons += 1);
+        self.inner.gt(&o.inner)
+    }
+
+    fn ge(&self, o: &CountingField<T>) -> bool {
+        tally(|c| c.comparisons += 1);
+        self.inner.ge(&o.inner)
+    }
+
+    fn e(&self, o: &CountingField<T>) -> bool {
+        tally(|c| c.comparisons += 1);
+        self.inner.e(&o.inner)
+    }
+}
+
+impl<T: INormed> INormed for CountingField<T> {
+    fn norm(&self) -> f64 {
+        tally(|c| c.math += 1);
+        self.inner.norm()
+    }
+}
+
+// root computation is setup work and is not counted, only what the kernel does with the roots
+impl<T: IPrimitiveRoots<T>> IPrimitiveRoots<CountingField<T>> for CountingField<T> {
+    fn primitive_root(&self, p: u64) -> CountingField<T> {
+        CountingField::new(self.inner.primitive_root(p))
+    }
+
+    fn pow(&self, exp: u64) -> CountingField<T> {
+        CountingField::new(self.inner.pow(exp))
+    }
+
+    fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<CountingField<T>> {
+        self.inner.precomputeRootsOfUnity(n, direction).into_iter().map(CountingField::new).collect()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for CountingField<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<T: Hash> Hash for CountingField<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
+impl<T: PartialEq> PartialEq for CountingField<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T: Eq> Eq for CountingField<T> {}
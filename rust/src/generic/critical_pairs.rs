@@ -0,0 +1,195 @@
+use std::cmp::Ordering;
+use std::fmt;
+use crate::generic::i_exponent::IExponent;
+use crate::generic::monomial_order::MonomialOrder;
+
+// which pair the queue hands out next, ties go to the pair created first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PairStrategy {
+    // in the order the pairs were created, as naive_grobner_basis does
+    Fifo,
+    // smallest lcm of the leading monomials in the term order
+    Normal,
+    // smallest sugar degree, the degree the S-polynomial would have if the input were
+    // homogenized, then as Normal
+    Sugar,
+    // smallest total degree of the lcm, then as Normal
+    Degree,
+}
+
+impl fmt::Display for PairStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PairStrategy::Fifo => "fifo",
+            PairStrategy::Normal => "normal",
+            PairStrategy::Sugar => "sugar",
+            PairStrategy::Degree => "degree",
+        };
+        f.pad(name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PairStats {
+    pub created: usize,
+    // leading monomials without a common variable, the S-polynomial reduces to zero
+    pub product: usize,
+    // new pairs whose lcm is a proper multiple of another new pair's lcm, or equal to it
+    // (Gebauer-Moller M and F)
+    pub chain: usize,
+    // queued pairs whose lcm the new leading monomial divides without giving either
+    // element the same lcm with it (Gebauer-Moller B)
+    pub queued: usize,
+    // S-polynomials computed and reduced, and how many of them reduced to zero
+    pub reduced: usize,
+    pub zero: usize,
+}
+
+impl PairStats {
+    pub fn pruned(&self) -> usize {
+        self.product + self.chain + self.queued
+    }
+}
+
+impl fmt::Display for PairStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pairs {} created, {} pruned (product {}, chain {}, queued {}), {} reduced, {} to zero",
+            self.created, self.pruned(), self.product, self.chain, self.queued, self.reduced, self.zero)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Pair<E> {
+    i: usize,
+    j: usize,
+    lcm: E,
+    sugar: u32,
+    serial: usize,
+}
+
+// the S-pairs still to reduce, by leading monomial only. With `criteria` each insert is the
+// Gebauer-Moller update: new pairs go through the product and chain criteria, queued pairs
+// through the chain criterion against the new element, and elements whose leading monomial
+// the new one divides stop taking part in new pairs. Without it every pair is queued
+pub struct PairQueue<E: IExponent + Clone + PartialEq, O: MonomialOrder> {
+    strategy: PairStrategy,
+    criteria: bool,
+    order: O,
+    leads: Vec<E>,
+    sugars: Vec<u32>,
+    active: Vec<bool>,
+    pairs: Vec<Pair<E>>,
+    pub stats: PairStats,
+}
+
+impl<E: IExponent + Clone + PartialEq, O: MonomialOrder> PairQueue<E, O> {
+    pub fn new(strategy: PairStrategy, criteria: bool, order: O) -> Self {
+        PairQueue { strategy, criteria, order, leads: Vec::new(), sugars: Vec::new(), active: Vec::new(), pairs: Vec::new(), stats: PairStats::default() }
+    }
+
+    fn pair(&mut self, i: usize, j: usize) -> Pair<E> {
+        let lcm = self.leads[i].lcm(&self.leads[j]);
+        let degree = lcm.degree();
+        let sugar = (self.sugars[i] + degree - self.leads[i].degree()).max(self.sugars[j] + degree - self.leads[j].degree());
+        self.stats.created += 1;
+        Pair { i, j, lcm, sugar, serial: self.stats.created }
+    }
+
+    fn coprime(&self, pair: &Pair<E>) -> bool {
+        pair.lcm.degree() == self.leads[pair.i].degree() + self.leads[pair.j].degree()
+    }
+
+    // the next basis element, with leading monomial `lead` and sugar degree `sugar` (the
+    // total degree for an input polynomial, the sugar of its pair otherwise). Returns its index
+    pub fn insert(&mut self, lead: E, sugar: u32) -> usize {
+        let h = self.leads.len();
+        self.leads.push(lead);
+        self.sugars.push(sugar);
+        self.active.push(true);
+        if !self.criteria {
+            for k in 0..h {
+                let pair = self.pair(k, h);
+                self.pairs.push(pair);
+            }
+            return h;
+        }
+
+        let active: Vec<usize> = (0..h).filter(|&k| self.active[k]).collect();
+        let mut candidates: Vec<Pair<E>> = active.into_iter().map(|k| self.pair(k, h)).collect();
+        // a new pair goes when another one still in play has an lcm dividing its own. Coprime
+        // pairs are kept for this test, so of several pairs with the same lcm the coprime one
+        // survives and takes the others with it
+        let mut kept: Vec<Pair<E>> = Vec::with_capacity(candidates.len());
+        while let Some(pair) = candidates.pop() {
+            if self.coprime(&pair) || !candidates.iter().chain(&kept).any(|other| pair.lcm.can_reduce(&other.lcm)) {
+                kept.push(pair);
+            } else {
+                self.stats.chain += 1;
+            }
+        }
+        let before = kept.len();
+        kept.retain(|pair| !self.coprime(pair));
+        self.stats.product += before - kept.len();
+
+        let (leads, lead) = (&self.leads, &self.leads[h]);
+        let before = self.pairs.len();
+        self.pairs.retain(|pair| {
+            !pair.lcm.can_reduce(lead) || leads[pair.i].lcm(lead) == pair.lcm || leads[pair.j].lcm(lead) == pair.lcm
+        });
+        self.stats.queued += before - self.pairs.len();
+
+        for k in 0..h {
+            if self.active[k] && self.leads[k].can_reduce(lead) {
+                self.active[k] = false;
+            }
+        }
+        kept.reverse();
+        self.pairs.extend(kept);
+        h
+    }
+
+    fn compare(&self, a: &Pair<E>, b: &Pair<E>) -> Ordering {
+        let first = match self.strategy {
+            PairStrategy::Fifo => Ordering::Equal,
+            PairStrategy::Normal => self.order.compare(&a.lcm, &b.lcm),
+            PairStrategy::Sugar => a.sugar.cmp(&b.sugar).then_with(|| self.order.compare(&a.lcm, &b.lcm)),
+            PairStrategy::Degree => a.lcm.degree().cmp(&b.lcm.degree()).then_with(|| self.order.compare(&a.lcm, &b.lcm)),
+        };
+        first.then(a.serial.cmp(&b.serial))
+    }
+
+    // (i, j, sugar) of the next pair, i < j
+    pub fn pop(&mut self) -> Option<(usize, usize, u32)> {
+        let next = (0..self.pairs.len()).min_by(|&a, &b| self.compare(&self.pairs[a], &self.pairs[b]))?;
+        let pair = self.pairs.swap_remove(next);
+        Some((pair.i, pair.j, pair.sugar))
+    }
+
+    // every pair of the lowest degree at once, in the order they were created, as F4 takes
+    // them: the lowest sugar under Sugar, the lowest lcm degree otherwise
+    pub fn pop_lowest_degree(&mut self) -> Vec<(usize, usize, u32)> {
+        let sugar = self.strategy == PairStrategy::Sugar;
+        let degree = |pair: &Pair<E>| if sugar { pair.sugar } else { pair.lcm.degree() };
+        let Some(lowest) = self.pairs.iter().map(degree).min() else {
+            return Vec::new();
+        };
+        let (mut taken, rest): (Vec<Pair<E>>, Vec<Pair<E>>) = std::mem::take(&mut self.pairs).into_iter().partition(|pair| degree(pair) == lowest);
+        self.pairs = rest;
+        taken.sort_by_key(|pair| pair.serial);
+        taken.into_iter().map(|pair| (pair.i, pair.j, pair.sugar)).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    // whether element k still takes part in new pairs, i.e. no later leading monomial divides
+    // its own. Always true without criteria
+    pub fn is_active(&self, k: usize) -> bool {
+        self.active[k]
+    }
+}
@@ -0,0 +1,293 @@
+use std::fmt;
+use crate::generic::i_copiable::ICopiable;
+use crate::generic::i_field::IField;
+use crate::generic::field_ops::impl_field_ops;
+use crate::generic::i_math::IMath;
+use crate::generic::i_ordered::IOrdered;
+use crate::generic::i_normed::INormed;
+use std::hash::Hash;
+use std::cmp::Eq;
+
+// Double-double real: the unevaluated sum hi + lo with |lo| <= ulp(hi)/2, about 106 bits of
+// mantissa. Used as the reference answer when measuring the error of SingleField/DoubleField runs
+#[derive(Debug)]
+pub struct DoubleDoubleField {
+    pub hi: f64,
+    pub lo: f64,
+}
+
+// s + e == a + b exactly
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+    let e = (a - (s - bb)) + (b - bb);
+    (s, e)
+}
+
+// same as two_sum but requires |a| >= |b|
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let e = b - (s - a);
+    (s, e)
+}
+
+// p + e == a * b exactly
+fn two_prod(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    let e = a.mul_add(b, -p);
+    (p, e)
+}
+
+impl DoubleDoubleField {
+    pub fn new(hi: f64, lo: f64) -> Self {
+        let (hi, lo) = two_sum(hi, lo);
+        DoubleDoubleField { hi, lo }
+    }
+
+    pub fn from_f64(value: f64) -> Self {
+        DoubleDoubleField { hi: value, lo: 0.0 }
+    }
+
+    fn add_dd(&self, o: &DoubleDoubleField) -> DoubleDoubleField {
+        let (s1, s2) = two_sum(self.hi, o.hi);
+        let (t1, t2) = two_sum(self.lo, o.lo);
+        let (s1, s2) = quick_two_sum(s1, s2 + t1);
+        let (hi, lo) = quick_two_sum(s1, s2 + t2);
+        DoubleDoubleField { hi, lo }
+    }
+
+    fn neg_dd(&self) -> DoubleDoubleField {
+        DoubleDoubleField { hi: -self.hi, lo: -self.lo }
+    }
+
+    fn mul_dd(&self, o: &DoubleDoubleField) -> DoubleDoubleField {
+        let (p1, p2) = two_prod(self.hi, o.hi);
+        let p2 = p2 + (self.hi * o.lo + self.lo * o.hi);
+        let (hi, lo) = quick_two_sum(p1, p2);
+        DoubleDoubleField { hi, lo }
+    }
+
+    // long division, three f64 quotient digits
+    fn div_dd(&self, o: &DoubleDoubleField) -> DoubleDoubleField {
+        let q1 = self.hi / o.hi;
+        let r = self.add_dd(&o.mul_dd(&DoubleDoubleField::from_f64(q1)).neg_dd());
+        let q2 = r.hi / o.hi;
+        let r = r.add_dd(&o.mul_dd(&DoubleDoubleField::from_f64(q2)).neg_dd());
+        let q3 = r.hi / o.hi;
+        let (hi, lo) = quick_two_sum(q1, q2);
+        DoubleDoubleField { hi, lo }.add_dd(&DoubleDoubleField::from_f64(q3))
+    }
+
+    fn compare(&self, o: &DoubleDoubleField) -> Option<std::cmp::Ordering> {
+        match self.hi.partial_cmp(&o.hi) {
+            Some(std::cmp::Ordering::Equal) => self.lo.partial_cmp(&o.lo),
+            ordering => ordering,
+        }
+    }
+
+    // cos(2 pi / 2^k) and sin(2 pi / 2^k) for k = 0..=levels, by repeated half angles from -1,
+    // so the roots of unity used by the FFT are accurate to double-double precision
+    pub fn half_angle_roots(levels: u32) -> Vec<(DoubleDoubleField, DoubleDoubleField)> {
+        let one = DoubleDoubleField::from_f64(1.0);
+        let half = DoubleDoubleField::from_f64(0.5);
+        let mut roots = vec![(one.copy(), DoubleDoubleField::from_f64(0.0))];
+        if levels == 0 {
+            return roots;
+        }
+        roots.push((one.neg_dd(), DoubleDoubleField::from_f64(0.0)));
+        for k in 2..=levels as usize {
+            let (c, s) = &roots[k - 1];
+            // cos(t/2) = sqrt((1 + cos t)/2), sin(t/2) = sin t / (2 cos(t/2)), t/2 in (0, pi/2]
+            let mut c_half_sq = one.add_dd(c).mul_dd(&half);
+            let c_half = c_half_sq.sqrt();
+            let s_half = if k == 2 {
+                one.copy()
+            } else {
+                s.div_dd(&c_half.add_dd(&c_half))
+            };
+            roots.push((c_half, s_half));
+        }
+        roots
+    }
+}
+
+impl IField for DoubleDoubleField {
+    fn a(&self, o: &DoubleDoubleField) -> DoubleDoubleField {
+        self.add_dd(o)
+    }
+
+    fn ae(&mut self, o: &DoubleDoubleField) {
+        *self = self.add_dd(o);
+    }
+
+    fn s(&self, o: &DoubleDoubleField) -> DoubleDoubleField {
+        self.add_dd(&o.neg_dd())
+    }
+
+    fn se(&mut self, o: &DoubleDoubleField) {
+        *self = self.add_dd(&o.neg_dd());
+    }
+
+    fn m(&self, o: &DoubleDoubleField) -> DoubleDoubleField {
+        self.mul_dd(o)
+    }
+
+    fn me(&mut self, o: &DoubleDoubleField) {
+        *self = self.mul_dd(o);
+    }
+
+    fn d(&self, o: &DoubleDoubleField) -> DoubleDoubleField {
+        self.div_dd(o)
+    }
+
+    fn de(&mut self, o: &DoubleDoubleField) {
+        *self = self.div_dd(o);
+    }
+
+    fn coerce_to_f64(&self) -> f64 {
+        self.hi + self.lo
+    }
+
+    fn coerce_from_int(&self, value: i32) -> Self {
+        DoubleDoubleField::from_f64(value as f64)
+    }
+
+    fn coerce(&self, value: f64) -> DoubleDoubleField {
+        DoubleDoubleField::from_f64(value)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.hi == 0.0
+    }
+
+    fn is_one(&self) -> bool {
+        self.hi == 1.0 && self.lo == 0.0
+    }
+
+    fn zero(&self) -> DoubleDoubleField {
+        DoubleDoubleField::from_f64(0.0)
+    }
+
+    fn one(&self) -> DoubleDoubleField {
+        DoubleDoubleField::from_f64(1.0)
+    }
+
+    fn new_zero() -> DoubleDoubleField {
+        DoubleDoubleField::from_f64(0.0)
+    }
+
+    fn new_one() -> DoubleDoubleField {
+        DoubleDoubleField::from_f64(1.0)
+    }
+}
+
+impl_field_ops!(DoubleDoubleField);
+
+impl ICopiable for DoubleDoubleField {
+    fn copy(&self) -> Self {
+        DoubleDoubleField { hi: self.hi, lo: self.lo }
+    }
+}
+
+impl Clone for DoubleDoubleField {
+    fn clone(&self) -> Self {
+        self.copy()
+    }
+}
+
+impl IMath for DoubleDoubleField {
+    fn abs(&self) -> DoubleDoubleField {
+        if self.hi < 0.0 { self.neg_dd() } else { self.copy() }
+    }
+
+    // one Newton step from the f64 root doubles the number of correct bits
+    fn sqrt(&mut self) -> DoubleDoubleField {
+        if self.hi <= 0.0 {
+            return DoubleDoubleField::from_f64(self.hi.sqrt());
+        }
+        let x = DoubleDoubleField::from_f64(self.hi.sqrt());
+        let residual = self.add_dd(&x.mul_dd(&x).neg_dd());
+        x.add_dd(&DoubleDoubleField::from_f64(residual.hi / (2.0 * x.hi)))
+    }
+
+    fn try_sqrt(&self) -> Option<DoubleDoubleField> {
+        if self.hi < 0.0 { None } else { Some(self.copy().sqrt()) }
+    }
+}
+
+impl IOrdered for DoubleDoubleField {
+    fn lt(&self, o: &DoubleDoubleField) -> bool {
+        self.compare(o) == Some(std::cmp::Ordering::Less)
+    }
+
+    fn le(&self, o: &DoubleDoubleField) -> bool {
+        matches!(self.compare(o), Some(std::cmp::Ordering::Less | std::cmp::Ordering::Equal))
+    }
+
+    fn gt(&self, o: &DoubleDoubleField) -> bool {
+        self.compare(o) == Some(std::cmp::Ordering::Greater)
+    }
+
+    fn ge(&self, o: &DoubleDoubleField) -> bool {
+        matches!(self.compare(o), Some(std::cmp::Ordering::Greater | std::cmp::Ordering::Equal))
+    }
+
+    fn e(&self, o: &DoubleDoubleField) -> bool {
+        self.hi == o.hi && self.lo == o.lo
+    }
+}
+
+impl INormed for DoubleDoubleField {
+    fn norm(&self) -> f64 {
+        (self.hi + self.lo).abs()
+    }
+}
+
+// 32 significant digits in scientific notation
+impl fmt::Display for DoubleDoubleField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.hi == 0.0 || !self.hi.is_finite() {
+            return write!(f, "{}", self.hi);
+        }
+        let ten = DoubleDoubleField::from_f64(10.0);
+        let mut r = self.abs();
+        let mut exponent = r.hi.log10().floor() as i32;
+        let mut scale = DoubleDoubleField::from_f64(1.0);
+        for _ in 0..exponent.unsigned_abs() {
+            scale = scale.mul_dd(&ten);
+        }
+        r = if exponent >= 0 { r.div_dd(&scale) } else { r.mul_dd(&scale) };
+        // log10 of hi can be off by one near powers of ten
+        if r.hi >= 10.0 {
+            r = r.div_dd(&ten);
+            exponent += 1;
+        } else if r.hi < 1.0 {
+            r = r.mul_dd(&ten);
+            exponent -= 1;
+        }
+        let mut digits = Vec::with_capacity(32);
+        for _ in 0..32 {
+            let digit = r.hi.floor().clamp(0.0, 9.0);
+            digits.push(digit as u8);
+            r = r.add_dd(&DoubleDoubleField::from_f64(-digit)).mul_dd(&ten);
+        }
+        let sign = if self.hi < 0.0 { "-" } else { "" };
+        let mantissa: String = digits[1..].iter().map(|d| (b'0' + d) as char).collect();
+        write!(f, "{}{}.{}e{}", sign, digits[0], mantissa, exponent)
+    }
+}
+
+impl Hash for DoubleDoubleField {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.hi.to_bits().hash(state);
+        self.lo.to_bits().hash(state);
+    }
+}
+
+impl PartialEq for DoubleDoubleField {
+    fn eq(&self, other: &Self) -> bool {
+        self.hi == other.hi && self.lo == other.lo
+    }
+}
+
+impl Eq for DoubleDoubleField {}
@@ -1,8 +1,11 @@
 use std::fmt;
 use crate::generic::i_copiable::ICopiable;
 use crate::generic::i_field::IField;
+use crate::generic::field_ops::{impl_field_ops, impl_num_traits};
 use crate::generic::i_math::IMath;
 use crate::generic::i_ordered::IOrdered;
+use crate::generic::i_normed::INormed;
+use crate::generic::i_rounding::IRounding;
 use std::hash::Hash;
 use std::cmp::Eq;
 #[derive(Debug)]
@@ -93,6 +96,26 @@ impl IField for DoubleField {
     fn one(&self) -> DoubleField {
         DoubleField::new(1.0)
     }
+
+    fn new_zero() -> DoubleField {
+        DoubleField::new(0.0)
+    }
+
+    fn new_one() -> DoubleField {
+        DoubleField::new(1.0)
+    }
+}
+
+impl_field_ops!(DoubleField);
+
+impl_num_traits!(DoubleField);
+
+impl num_traits::Num for DoubleField {
+    type FromStrRadixErr = num_traits::ParseFloatError;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<DoubleField, num_traits::ParseFloatError> {
+        <f64 as num_traits::Num>::from_str_radix(s, radix).map(DoubleField::new)
+    }
 }
 
 impl ICopiable for DoubleField {
@@ -114,6 +137,22 @@ impl IMath for DoubleField {
     fn sqrt(&mut self) -> DoubleField{
         DoubleField::new(self.d.sqrt())
     }
+
+    fn try_sqrt(&self) -> Option<DoubleField> {
+        if self.d < 0.0 { None } else { Some(DoubleField::new(self.d.sqrt())) }
+    }
+
+    fn exp(&self) -> DoubleField {
+        DoubleField::new(self.d.exp())
+    }
+
+    fn ln(&self) -> DoubleField {
+        DoubleField::new(self.d.ln())
+    }
+
+    fn powf(&self, exponent: &DoubleField) -> DoubleField {
+        DoubleField::new(self.d.powf(exponent.d))
+    }
 }
 
 impl IOrdered for DoubleField {
@@ -138,6 +177,23 @@ impl IOrdered for DoubleField {
     }
 }
 
+impl INormed for DoubleField {
+    fn norm(&self) -> f64 {
+        self.d.abs()
+    }
+}
+
+
+// one ulp in each direction, enough to enclose a round-to-nearest result
+impl IRounding for DoubleField {
+    fn next_up(&self) -> DoubleField {
+        DoubleField::new(self.d.next_up())
+    }
+
+    fn next_down(&self) -> DoubleField {
+        DoubleField::new(self.d.next_down())
+    }
+}
 
 impl fmt::Display for DoubleField {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -0,0 +1,235 @@
+use std::fmt;
+use crate::generic::i_copiable::ICopiable;
+use crate::generic::i_field::IField;
+use crate::generic::field_error::FieldError;
+use crate::generic::field_ops::impl_field_ops;
+use crate::generic::i_math::IMath;
+use crate::generic::i_ordered::IOrdered;
+use crate::generic::i_normed::INormed;
+use std::hash::Hash;
+use std::cmp::Eq;
+
+// Dual number value + derivative * eps with eps^2 = 0, i.e. forward mode automatic
+// differentiation: run any generic kernel on DualField and the derivative of every
+// result with respect to the one input seeded with DualField::variable comes along
+#[derive(Debug)]
+pub struct DualField<T> {
+    pub value: T,
+    pub derivative: T,
+}
+
+impl<T: IField + ICopiable> DualField<T> {
+    pub fn new(value: T, derivative: T) -> Self {
+        DualField { value, derivative }
+    }
+
+    // d/dx x = 1
+    pub fn variable(value: T) -> Self {
+        let derivative = value.one();
+        DualField { value, derivative }
+    }
+
+    pub fn constant(value: T) -> Self {
+        let derivative = value.zero();
+        DualField { value, derivative }
+    }
+}
+
+impl<T: IField + ICopiable> IField for DualField<T> {
+    fn a(&self, o: &DualField<T>) -> DualField<T> {
+        DualField::new(self.value.a(&o.value), self.derivative.a(&o.derivative))
+    }
+
+    fn ae(&mut self, o: &DualField<T>) {
+        self.value.ae(&o.value);
+        self.derivative.ae(&o.derivative);
+    }
+
+    fn s(&self, o: &DualField<T>) -> DualField<T> {
+        DualField::new(self.value.s(&o.value), self.derivative.s(&o.derivative))
+    }
+
+    fn se(&mut self, o: &DualField<T>) {
+        self.value.se(&o.value);
+        self.derivative.se(&o.derivative);
+    }
+
+    // (a + a'e)(b + b'e) = ab + (a'b + ab')e
+    fn m(&self, o: &DualField<T>) -> DualField<T> {
+        DualField::new(
+            self.value.m(&o.value),
+            self.derivative.m(&o.value).a(&self.value.m(&o.derivative)),
+        )
+    }
+
+    fn me(&mut self, o: &DualField<T>) {
+        *self = self.m(o);
+    }
+
+    // (a + a'e)/(b + b'e) = a/b + ((a'b - ab')/b^2)e
+    fn d(&self, o: &DualField<T>) -> DualField<T> {
+        let value = self.value.d(&o.value);
+        let derivative = self.derivative.s(&value.m(&o.derivative)).d(&o.value);
+        DualField::new(value, derivative)
+    }
+
+    fn de(&mut self, o: &DualField<T>) {
+        *self = self.d(o);
+    }
+
+    // only the value part of the divisor has to be invertible, a zero value with a nonzero
+    // derivative passes is_zero but still divides by zero
+    fn checked_d(&self, o: &DualField<T>) -> Result<DualField<T>, FieldError> {
+        let value = self.value.checked_d(&o.value)?;
+        let derivative = self.derivative.s(&value.m(&o.derivative)).d(&o.value);
+        Ok(DualField::new(value, derivative))
+    }
+
+    fn coerce_to_f64(&self) -> f64 {
+        self.value.coerce_to_f64()
+    }
+
+    fn coerce_from_int(&self, value: i32) -> Self {
+        DualField::constant(self.value.coerce_from_int(value))
+    }
+
+    fn coerce(&self, value: f64) -> DualField<T> {
+        DualField::constant(self.value.coerce(value))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value.is_zero() && self.derivative.is_zero()
+    }
+
+    fn is_one(&self) -> bool {
+        self.value.is_one() && self.derivative.is_zero()
+    }
+
+    fn zero(&self) -> DualField<T> {
+        DualField::constant(self.value.zero())
+    }
+
+    fn one(&self) -> DualField<T> {
+        DualField::constant(self.value.one())
+    }
+
+    fn new_zero() -> DualField<T> {
+        DualField::constant(T::new_zero())
+    }
+
+    fn new_one() -> DualField<T> {
+        DualField::constant(T::new_one())
+    }
+}
+
+impl_field_ops!([T: IField + ICopiable] DualField<T>);
+
+impl<T: ICopiable> ICopiable for DualField<T> {
+    fn copy(&self) -> Self {
+        DualField { value: self.value.copy(), derivative: self.derivative.copy() }
+    }
+}
+
+impl<T: ICopiable> Clone for DualField<T> {
+    fn clone(&self) -> Self {
+        self.copy()
+    }
+}
+
+impl<T: IField + ICopiable + IMath + IOrdered> IMath for DualField<T> {
+    fn abs(&self) -> DualField<T> {
+        if self.value.lt(&self.value.zero()) {
+            self.zero().s(self)
+        } else {
+            self.copy()
+        }
+    }
+
+    // d sqrt(x) = x' / (2 sqrt(x))
+    fn sqrt(&mut self) -> DualField<T> {
+        let root = self.value.copy().sqrt();
+        let derivative = self.derivative.d(&root.a(&root));
+        DualField::new(root, derivative)
+    }
+
+    fn try_sqrt(&self) -> Option<DualField<T>> {
+        let root = self.value.try_sqrt()?;
+        let derivative = self.derivative.d(&root.a(&root));
+        Some(DualField::new(root, derivative))
+    }
+
+    fn conj(&self) -> DualField<T> {
+        DualField::new(self.value.conj(), self.derivative.conj())
+    }
+
+    // d exp(x) = x' exp(x)
+    fn exp(&self) -> DualField<T> {
+        let value = self.value.exp();
+        let derivative = self.derivative.m(&value);
+        DualField::new(value, derivative)
+    }
+
+    // d ln(x) = x' / x
+    fn ln(&self) -> DualField<T> {
+        DualField::new(self.value.ln(), self.derivative.d(&self.value))
+    }
+
+    // d x^y = x^y (y' ln x + y x' / x)
+    fn powf(&self, exponent: &DualField<T>) -> DualField<T> {
+        let value = self.value.powf(&exponent.value);
+        let rate = exponent.derivative.m(&self.value.ln()).a(&exponent.value.m(&self.derivative).d(&self.value));
+        let derivative = value.m(&rate);
+        DualField::new(value, derivative)
+    }
+}
+
+// ordered by value, which is what pivoting and comparisons in the kernels expect
+impl<T: IOrdered> IOrdered for DualField<T> {
+    fn lt(&self, o: &DualField<T>) -> bool {
+        self.value.lt(&o.value)
+    }
+
+    fn le(&self, o: &DualField<T>) -> bool {
+        self.value.le(&o.value)
+    }
+
+    fn gt(&self, o: &DualField<T>) -> bool {
+        self.value.gt(&o.value)
+    }
+
+    fn ge(&self, o: &DualField<T>) -> bool {
+        self.value.ge(&o.value)
+    }
+
+    fn e(&self, o: &DualField<T>) -> bool {
+        self.value.e(&o.value) && self.derivative.e(&o.derivative)
+    }
+}
+
+// the size of the value, the derivative does not take part in pivoting
+impl<T: INormed> INormed for DualField<T> {
+    fn norm(&self) -> f64 {
+        self.value.norm()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for DualField<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} + {}e", self.value, self.derivative)
+    }
+}
+
+impl<T: Hash> Hash for DualField<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.derivative.hash(state);
+    }
+}
+
+impl<T: PartialEq> PartialEq for DualField<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.derivative == other.derivative
+    }
+}
+
+impl<T: Eq> Eq for DualField<T> {}
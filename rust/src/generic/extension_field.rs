@@ -0,0 +1,544 @@
+use std::fmt;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use crate::generic::i_field::IField;
+use crate::generic::field_ops::impl_field_ops;
+use crate::generic::i_normed::INormed;
+use crate::generic::i_math::IMath;
+use crate::generic::i_primitive_roots::IPrimitiveRoots;
+use crate::generic::roots_of_unity::{roots_of_unity, powers};
+use std::sync::Arc;
+use crate::generic::i_copiable::ICopiable;
+use crate::generic::int_mod_p::{mod_inverse, mul_mod};
+use std::hash::Hash;
+use std::cmp::Eq;
+
+// GF(P^K) as polynomials over GF(P) modulo a monic irreducible of degree K.
+// Coefficients are stored lowest degree first. The irreducible is picked once per (P, K)
+// (the first one in lexicographic order unless set_modulus_poly was called) and every
+// element keeps a pointer to it, so arithmetic never has to look it up
+#[derive(Debug)]
+pub struct ExtensionField<const P: u64, const K: usize> {
+    pub coeffs: [u64; K],
+    modulus: &'static [u64],
+}
+
+type ModulusRegistry = Mutex<HashMap<(u64, usize), &'static [u64]>>;
+
+static MODULUS_POLYS: OnceLock<ModulusRegistry> = OnceLock::new();
+
+fn registered_modulus(p: u64, k: usize) -> &'static [u64] {
+    let mut registry = MODULUS_POLYS.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap();
+    registry
+        .entry((p, k))
+        .or_insert_with(|| Box::leak(find_irreducible(p, k).into_boxed_slice()))
+}
+
+// ---- polynomial helpers over GF(p), coefficient vectors lowest degree first ----
+
+fn poly_trim(a: &mut Vec<u64>) {
+    while a.last() == Some(&0) {
+        a.pop();
+    }
+}
+
+fn poly_sub(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+    let mut result = vec![0; a.len().max(b.len())];
+    for (i, r) in result.iter_mut().enumerate() {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        *r = if x >= y { x - y } else { x + p - y };
+    }
+    poly_trim(&mut result);
+    result
+}
+
+fn poly_mul(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut result = vec![0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        if x == 0 {
+            continue;
+        }
+        for (j, &y) in b.iter().enumerate() {
+            result[i + j] = (result[i + j] + mul_mod(x, y, p)) % p;
+        }
+    }
+    poly_trim(&mut result);
+    result
+}
+
+// quotient and remainder of a / b, b must be non-zero
+fn poly_divmod(a: &[u64], b: &[u64], p: u64) -> (Vec<u64>, Vec<u64>) {
+    let mut r = a.to_vec();
+    poly_trim(&mut r);
+    let db = b.len() - 1;
+    if r.len() < b.len() {
+        return (Vec::new(), r);
+    }
+    let lead_inv = mod_inverse(b[db], p);
+    let mut q = vec![0; r.len() - db];
+    for i in (0..q.len()).rev() {
+        let c = mul_mod(r[i + db], lead_inv, p);
+        q[i] = c;
+        if c == 0 {
+            continue;
+        }
+        for (j, &bj) in b.iter().enumerate() {
+            let t = mul_mod(c, bj, p);
+            r[i + j] = if r[i + j] >= t { r[i + j] - t } else { r[i + j] + p - t };
+        }
+    }
+    poly_trim(&mut q);
+    poly_trim(&mut r);
+    (q, r)
+}
+
+fn poly_gcd(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+    let mut x = a.to_vec();
+    let mut y = b.to_vec();
+    poly_trim(&mut x);
+    poly_trim(&mut y);
+    while !y.is_empty() {
+        let (_, r) = poly_divmod(&x, &y, p);
+        x = y;
+        y = r;
+    }
+    x
+}
+
+fn poly_pow_mod(base: &[u64], mut exp: u128, f: &[u64], p: u64) -> Vec<u64> {
+    let mut result = vec![1];
+    let mut base = poly_divmod(base, f, p).1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = poly_divmod(&poly_mul(&result, &base, p), f, p).1;
+        }
+        base = poly_divmod(&poly_mul(&base, &base, p), f, p).1;
+        exp >>= 1;
+    }
+    result
+}
+
+fn prime_factors(mut n: u128) -> Vec<u128> {
+    let mut factors = Vec::new();
+    let mut i: u128 = 2;
+    while i <= n / i {
+        if n.is_multiple_of(i) {
+            factors.push(i);
+            while n.is_multiple_of(i) {
+                n /= i;
+            }
+        }
+        i += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    factors
+}
+
+// Rabin's test: f of degree k is irreducible iff x^(p^k) = x mod f and
+// gcd(x^(p^(k/r)) - x, f) = 1 for every prime r dividing k
+pub fn is_irreducible(f: &[u64], p: u64) -> bool {
+    let k = f.len() - 1;
+    if k == 0 || f[k] == 0 {
+        return false;
+    }
+    if k == 1 {
+        return true;
+    }
+    let x = vec![0, 1];
+    // x^(p^i) mod f for i = 0..=k
+    let mut powers = vec![x.clone()];
+    for i in 1..=k {
+        let next = poly_pow_mod(&powers[i - 1], p as u128, f, p);
+        powers.push(next);
+    }
+    if poly_sub(&powers[k], &x, p).iter().any(|&c| c != 0) {
+        return false;
+    }
+    for r in prime_factors(k as u128) {
+        let g = poly_gcd(f, &poly_sub(&powers[k / r as usize], &x, p), p);
+        if g.len() != 1 {
+            return false;
+        }
+    }
+    true
+}
+
+// first monic irreducible of degree k, counting the lower coefficients in base p
+pub fn find_irreducible(p: u64, k: usize) -> Vec<u64> {
+    let mut f = vec![0; k + 1];
+    f[k] = 1;
+    loop {
+        if is_irreducible(&f, p) {
+            return f;
+        }
+        // increment the base p counter f[0..k]
+        let mut i = 0;
+        while i < k {
+            f[i] += 1;
+            if f[i] < p {
+                break;
+            }
+            f[i] = 0;
+            i += 1;
+        }
+        if i == k {
+            panic!("No irreducible polynomial of degree {} over GF({})", k, p);
+        }
+    }
+}
+
+impl<const P: u64, const K: usize> ExtensionField<P, K> {
+    pub fn new(coeffs: [u64; K]) -> Self {
+        let mut coeffs = coeffs;
+        for c in coeffs.iter_mut() {
+            *c %= P;
+        }
+        ExtensionField { coeffs, modulus: registered_modulus(P, K) }
+    }
+
+    // an element of the prime subfield
+    pub fn from_int(value: u64) -> Self {
+        let mut coeffs = [0; K];
+        coeffs[0] = value % P;
+        ExtensionField { coeffs, modulus: registered_modulus(P, K) }
+    }
+
+    // the class of x, a root of the modulus polynomial
+    pub fn generator() -> Self {
+        let mut coeffs = [0; K];
+        if K > 1 {
+            coeffs[1] = 1;
+        } else {
+            coeffs[0] = (P - registered_modulus(P, K)[0]) % P;
+        }
+        ExtensionField { coeffs, modulus: registered_modulus(P, K) }
+    }
+
+    // Use a specific monic irreducible (lowest degree first, K + 1 coefficients), e.g. the AES
+    // polynomial for GF(2^8). Must be called before any element of GF(P^K) is created
+    pub fn set_modulus_poly(poly: &[u64]) {
+        if poly.len() != K + 1 || poly[K] != 1 || !is_irreducible(poly, P) {
+            panic!("Modulus polynomial must be monic, irreducible and of degree {}", K);
+        }
+        let mut registry = MODULUS_POLYS.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap();
+        registry.insert((P, K), Box::leak(poly.to_vec().into_boxed_slice()));
+    }
+
+    pub fn modulus_poly(&self) -> &'static [u64] {
+        self.modulus
+    }
+
+    // number of elements, P^K
+    pub fn order() -> u128 {
+        (P as u128).pow(K as u32)
+    }
+
+    fn with_coeffs(&self, coeffs: [u64; K]) -> Self {
+        ExtensionField { coeffs, modulus: self.modulus }
+    }
+
+    fn with_poly(&self, poly: &[u64]) -> Self {
+        let mut coeffs = [0; K];
+        coeffs[..poly.len()].copy_from_slice(poly);
+        self.with_coeffs(coeffs)
+    }
+
+    fn to_poly(&self) -> Vec<u64> {
+        let mut poly = self.coeffs.to_vec();
+        poly_trim(&mut poly);
+        poly
+    }
+
+    fn mul_coeffs(&self, o: &Self) -> [u64; K] {
+        let mut product = vec![0u64; 2 * K - 1];
+        for (i, &x) in self.coeffs.iter().enumerate() {
+            if x == 0 {
+                continue;
+            }
+            for (j, &y) in o.coeffs.iter().enumerate() {
+                product[i + j] = (product[i + j] + mul_mod(x, y, P)) % P;
+            }
+        }
+        // x^K = -(f_0 + f_1 x + ... + f_{K-1} x^{K-1})
+        for i in (K..2 * K - 1).rev() {
+            let c = product[i];
+            if c == 0 {
+                continue;
+            }
+            for j in 0..K {
+                let t = mul_mod(c, self.modulus[j], P);
+                let idx = i - K + j;
+                product[idx] = if product[idx] >= t { product[idx] - t } else { product[idx] + P - t };
+            }
+        }
+        let mut coeffs = [0; K];
+        coeffs.copy_from_slice(&product[..K]);
+        coeffs
+    }
+
+    pub fn inverse(&self) -> Self {
+        if self.is_zero() {
+            panic!("Division by zero in ExtensionField");
+        }
+        // extended Euclid on (modulus, self)
+        let mut r0 = self.modulus.to_vec();
+        let mut r1 = self.to_poly();
+        let mut s0: Vec<u64> = Vec::new();
+        let mut s1: Vec<u64> = vec![1];
+        while !r1.is_empty() {
+            let (q, r) = poly_divmod(&r0, &r1, P);
+            let s = poly_sub(&s0, &poly_mul(&q, &s1, P), P);
+            r0 = r1;
+            r1 = r;
+            s0 = s1;
+            s1 = s;
+        }
+        // r0 is a non-zero constant
+        let c_inv = mod_inverse(r0[0], P);
+        let inv: Vec<u64> = s0.iter().map(|&c| mul_mod(c, c_inv, P)).collect();
+        self.with_poly(&inv)
+    }
+
+    pub fn pow_u128(&self, mut exp: u128) -> Self {
+        let mut result = self.one();
+        let mut base = self.copy();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result.me(&base);
+            }
+            base = base.m(&base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    // the Frobenius automorphism a -> a^P
+    pub fn frobenius(&self) -> Self {
+        self.pow_u128(P as u128)
+    }
+
+    // sum c_i P^i, a bijection onto 0..P^K used for ordering and coerce_to_f64
+    fn encode(&self) -> u128 {
+        self.coeffs.iter().rev().fold(0u128, |acc, &c| acc.wrapping_mul(P as u128).wrapping_add(c as u128))
+    }
+
+    // a generator of the multiplicative group, found by testing g^((q-1)/r) != 1 for each prime r | q-1
+    pub fn primitive_element(&self) -> Self {
+        let group_order = Self::order() - 1;
+        let factors = prime_factors(group_order);
+        // constants lie in GF(P)* and can only be primitive when K = 1, so start at a
+        let mut candidate: u128 = if K > 1 { P as u128 } else { 1 };
+        loop {
+            let mut coeffs = [0; K];
+            let mut t = candidate;
+            for c in coeffs.iter_mut() {
+                *c = (t % P as u128) as u64;
+                t /= P as u128;
+            }
+            let g = self.with_coeffs(coeffs);
+            if !g.is_zero() && factors.iter().all(|&r| !g.pow_u128(group_order / r).is_one()) {
+                return g;
+            }
+            candidate += 1;
+            if candidate >= Self::order() {
+                panic!("No primitive element found in GF({}^{})", P, K);
+            }
+        }
+    }
+}
+
+impl<const P: u64, const K: usize> IField for ExtensionField<P, K> {
+    fn a(&self, o: &Self) -> Self {
+        let mut coeffs = self.coeffs;
+        for (c, &d) in coeffs.iter_mut().zip(&o.coeffs) {
+            *c = (*c + d) % P;
+        }
+        self.with_coeffs(coeffs)
+    }
+
+    fn ae(&mut self, o: &Self) {
+        for (c, &d) in self.coeffs.iter_mut().zip(&o.coeffs) {
+            *c = (*c + d) % P;
+        }
+    }
+
+    fn s(&self, o: &Self) -> Self {
+        let mut coeffs = self.coeffs;
+        for (c, &d) in coeffs.iter_mut().zip(&o.coeffs) {
+            *c = (*c + P - d) % P;
+        }
+        self.with_coeffs(coeffs)
+    }
+
+    fn se(&mut self, o: &Self) {
+        for (c, &d) in self.coeffs.iter_mut().zip(&o.coeffs) {
+            *c = (*c + P - d) % P;
+        }
+    }
+
+    fn m(&self, o: &Self) -> Self {
+        self.with_coeffs(self.mul_coeffs(o))
+    }
+
+    fn me(&mut self, o: &Self) {
+        self.coeffs = self.mul_coeffs(o);
+    }
+
+    fn d(&self, o: &Self) -> Self {
+        self.m(&o.inverse())
+    }
+
+    fn de(&mut self, o: &Self) {
+        let inv = o.inverse();
+        self.me(&inv);
+    }
+
+    // non-zero exactly when the element is, like IntModP's representative
+    fn coerce_to_f64(&self) -> f64 {
+        self.encode() as f64
+    }
+
+    fn coerce_from_int(&self, value: i32) -> Self {
+        let mut coeffs = [0; K];
+        coeffs[0] = (value as i64).rem_euclid(P as i64) as u64;
+        self.with_coeffs(coeffs)
+    }
+
+    fn coerce(&self, value: f64) -> Self {
+        let mut coeffs = [0; K];
+        coeffs[0] = (value as u64) % P;
+        self.with_coeffs(coeffs)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|&c| c == 0)
+    }
+
+    fn is_one(&self) -> bool {
+        self.coeffs[0] == 1 && self.coeffs[1..].iter().all(|&c| c == 0)
+    }
+
+    fn zero(&self) -> Self {
+        self.with_coeffs([0; K])
+    }
+
+    fn one(&self) -> Self {
+        let mut coeffs = [0; K];
+        coeffs[0] = 1;
+        self.with_coeffs(coeffs)
+    }
+
+    fn new_zero() -> Self {
+        ExtensionField::from_int(0)
+    }
+
+    fn new_one() -> Self {
+        ExtensionField::from_int(1)
+    }
+}
+
+impl_field_ops!([const P: u64, const K: usize] ExtensionField<P, K>);
+
+impl<const P: u64, const K: usize> ICopiable for ExtensionField<P, K> {
+    fn copy(&self) -> Self {
+        self.with_coeffs(self.coeffs)
+    }
+}
+
+impl<const P: u64, const K: usize> Clone for ExtensionField<P, K> {
+    fn clone(&self) -> Self {
+        self.copy()
+    }
+}
+
+// the trivial absolute value, like IntModP's
+// the encoding, as IntModP's residue
+impl<const P: u64, const K: usize> INormed for ExtensionField<P, K> {
+    fn norm(&self) -> f64 {
+        self.encode() as f64
+    }
+}
+
+impl<const P: u64, const K: usize> IMath for ExtensionField<P, K> {
+    fn abs(&self) -> Self {
+        self.copy()
+    }
+
+    fn sqrt(&mut self) -> Self {
+        panic!("Square root not implemented for ExtensionField");
+    }
+}
+
+impl<const P: u64, const K: usize> IPrimitiveRoots<ExtensionField<P, K>> for ExtensionField<P, K> {
+    fn primitive_root(&self, _n: u64) -> Self {
+        self.primitive_element()
+    }
+
+    fn pow(&self, exp: u64) -> Self {
+        self.pow_u128(exp as u128)
+    }
+
+    fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<Self> {
+        self.roots_of_unity(n, direction).to_vec()
+    }
+
+    // keyed by P, the type carries K
+    fn roots_of_unity(&self, n: u32, direction: i32) -> Arc<Vec<Self>> {
+        roots_of_unity(P, n, direction, || {
+            let group_order = Self::order() - 1;
+            if group_order % n as u128 != 0 {
+                panic!("n must divide p^k-1 for roots of unity to exist in ExtensionField");
+            }
+            let omega = self.primitive_element().pow_u128(group_order / n as u128);
+            let step = if direction >= 0 { omega.copy() } else { omega.inverse() };
+            powers(&step, n)
+        })
+    }
+}
+
+// written in terms of the generator a, highest power first
+impl<const P: u64, const K: usize> fmt::Display for ExtensionField<P, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut first = true;
+        for (i, &c) in self.coeffs.iter().enumerate().rev() {
+            if c == 0 {
+                continue;
+            }
+            if !first {
+                write!(f, "+")?;
+            }
+            first = false;
+            match (i, c) {
+                (0, _) => write!(f, "{}", c)?,
+                (1, 1) => write!(f, "a")?,
+                (1, _) => write!(f, "{}a", c)?,
+                (_, 1) => write!(f, "a^{}", i)?,
+                (_, _) => write!(f, "{}a^{}", c, i)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<const P: u64, const K: usize> Hash for ExtensionField<P, K> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.coeffs.hash(state);
+    }
+}
+
+impl<const P: u64, const K: usize> PartialEq for ExtensionField<P, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coeffs == other.coeffs
+    }
+}
+
+impl<const P: u64, const K: usize> Eq for ExtensionField<P, K> {}
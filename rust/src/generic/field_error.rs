@@ -0,0 +1,24 @@
+use std::fmt;
+
+// Errors of the fallible IField operations (checked_d, try_inv) and of the kernels built on them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldError {
+    DivisionByZero,
+    // nonzero but not a unit, e.g. IntModP under a composite modulus or a complex number
+    // over GF(p) whose norm re^2 + im^2 vanishes
+    NotInvertible,
+    // gen_lu::factor found no nonzero pivot in this column
+    SingularMatrix { column: usize },
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::DivisionByZero => write!(f, "division by zero"),
+            FieldError::NotInvertible => write!(f, "element is not invertible"),
+            FieldError::SingularMatrix { column } => write!(f, "matrix is singular (zero pivot in column {})", column),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
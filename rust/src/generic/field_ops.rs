@@ -0,0 +1,122 @@
+// std::ops for IField types. A blanket impl over T: IField is not allowed by the orphan rules,
+// so each field opts in with one line:
+//     impl_field_ops!(DoubleField);
+//     impl_field_ops!([T: IField + ICopiable] ComplexField<T>);
+// Owned and borrowed operands are both accepted, every operator forwards to the IField method
+// (a/s/m/d and ae/se/me/de), so results and op counts match the method API exactly.
+// Neg is zero - x. IField must be in scope where the macro is used
+// the Gen* drivers each carry their own copy of generic/ that never expands the macro
+#[allow(unused_macros)]
+macro_rules! impl_field_ops {
+    ([$($g:tt)*] $t:ty) => {
+        impl_field_ops!(@binary [$($g)*] $t, Add, add, AddAssign, add_assign, a, ae);
+        impl_field_ops!(@binary [$($g)*] $t, Sub, sub, SubAssign, sub_assign, s, se);
+        impl_field_ops!(@binary [$($g)*] $t, Mul, mul, MulAssign, mul_assign, m, me);
+        impl_field_ops!(@binary [$($g)*] $t, Div, div, DivAssign, div_assign, d, de);
+
+        impl<$($g)*> std::ops::Neg for $t {
+            type Output = $t;
+            fn neg(self) -> $t {
+                self.zero().s(&self)
+            }
+        }
+
+        impl<'r, $($g)*> std::ops::Neg for &'r $t {
+            type Output = $t;
+            fn neg(self) -> $t {
+                self.zero().s(self)
+            }
+        }
+    };
+    ($t:ty) => {
+        impl_field_ops!([] $t);
+    };
+    (@binary [$($g:tt)*] $t:ty, $op:ident, $f:ident, $op_assign:ident, $f_assign:ident, $m:ident, $m_assign:ident) => {
+        impl<$($g)*> std::ops::$op for $t {
+            type Output = $t;
+            fn $f(self, o: $t) -> $t {
+                self.$m(&o)
+            }
+        }
+
+        impl<'r, $($g)*> std::ops::$op<&'r $t> for $t {
+            type Output = $t;
+            fn $f(self, o: &'r $t) -> $t {
+                self.$m(o)
+            }
+        }
+
+        impl<'l, 'r, $($g)*> std::ops::$op<&'r $t> for &'l $t {
+            type Output = $t;
+            fn $f(self, o: &'r $t) -> $t {
+                self.$m(o)
+            }
+        }
+
+        impl<$($g)*> std::ops::$op_assign for $t {
+            fn $f_assign(&mut self, o: $t) {
+                self.$m_assign(&o);
+            }
+        }
+
+        impl<'r, $($g)*> std::ops::$op_assign<&'r $t> for $t {
+            fn $f_assign(&mut self, o: &'r $t) {
+                self.$m_assign(o);
+            }
+        }
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use impl_field_ops;
+
+// num_traits::{Zero, One, Inv} and the Rem needed by num_traits::Num, on top of impl_field_ops.
+// Num itself is left to each field since from_str_radix depends on the representation.
+// Rem is the remainder of exact field division, always zero (x = (x / y) * y)
+#[allow(unused_macros)]
+macro_rules! impl_num_traits {
+    ([$($g:tt)*] $t:ty) => {
+        impl<$($g)*> num_traits::Zero for $t {
+            fn zero() -> $t {
+                <$t as $crate::generic::i_field::IField>::new_zero()
+            }
+
+            fn is_zero(&self) -> bool {
+                $crate::generic::i_field::IField::is_zero(self)
+            }
+        }
+
+        impl<$($g)*> num_traits::One for $t {
+            fn one() -> $t {
+                <$t as $crate::generic::i_field::IField>::new_one()
+            }
+        }
+
+        impl<$($g)*> num_traits::Inv for $t {
+            type Output = $t;
+            fn inv(self) -> $t {
+                $crate::generic::i_field::IField::d(&<$t as $crate::generic::i_field::IField>::new_one(), &self)
+            }
+        }
+
+        impl<'r, $($g)*> num_traits::Inv for &'r $t {
+            type Output = $t;
+            fn inv(self) -> $t {
+                $crate::generic::i_field::IField::d(&<$t as $crate::generic::i_field::IField>::new_one(), self)
+            }
+        }
+
+        impl<$($g)*> std::ops::Rem for $t {
+            type Output = $t;
+            fn rem(self, _o: $t) -> $t {
+                <$t as $crate::generic::i_field::IField>::new_zero()
+            }
+        }
+    };
+    ($t:ty) => {
+        impl_num_traits!([] $t);
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use impl_num_traits;
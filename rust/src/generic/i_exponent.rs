@@ -1,9 +1,15 @@
 pub trait IExponent {
     fn add(&self, o: &Self) -> Self;
+    // None when an exponent (or the degree) no longer fits the representation
+    fn checked_add(&self, o: &Self) -> Option<Self> where Self: Sized;
     fn sub(&self, o: &Self) -> Self;
     fn lcm(&self, other: &Self) -> Self;
     fn degree(&self) -> u32;
     fn lex_compare(&self, other: &Self) -> std::cmp::Ordering;
     fn can_reduce(&self, divisor: &Self) -> bool;
+    // exponent of variable var, 0 past the variables the representation holds
+    fn exponent(&self, var: usize) -> u32;
+    // number of variables held, for a sparse exponent one past the last present
+    fn vars(&self) -> usize;
 
 }
\ No newline at end of file
@@ -1,3 +1,5 @@
+use crate::generic::field_error::FieldError;
+
 pub trait IField: {
     fn a(&self, o: &Self) -> Self;
     fn ae(&mut self, o: &Self);
@@ -16,4 +18,25 @@ pub trait IField: {
     fn is_one(&self) -> bool;
     fn zero(&self) -> Self;
     fn one(&self) -> Self;
+
+    // static constructors, for call sites with no instance at hand (T::new_zero(), num_traits::Zero)
+    fn new_zero() -> Self;
+    fn new_one() -> Self;
+
+    fn new_from_int(value: i32) -> Self where Self: Sized {
+        Self::new_one().coerce_from_int(value)
+    }
+
+    // d and de may panic or silently return inf/NaN on a zero divisor, these report it instead
+    fn checked_d(&self, o: &Self) -> Result<Self, FieldError> where Self: Sized {
+        if o.is_zero() {
+            Err(FieldError::DivisionByZero)
+        } else {
+            Ok(self.d(o))
+        }
+    }
+
+    fn try_inv(&self) -> Result<Self, FieldError> where Self: Sized {
+        self.one().checked_d(self)
+    }
 }
\ No newline at end of file
@@ -1,4 +1,29 @@
+use crate::generic::i_copiable::ICopiable;
+
 pub trait IMath{
     fn abs(&self) -> Self;
     fn sqrt(&mut self) -> Self;
-}
\ No newline at end of file
+
+    // None when the field has no root: negative reals, quadratic non-residues mod p
+    fn try_sqrt(&self) -> Option<Self> where Self: Sized + ICopiable {
+        Some(self.copy().sqrt())
+    }
+
+    // the identity except over complex numbers
+    fn conj(&self) -> Self where Self: Sized + ICopiable {
+        self.copy()
+    }
+
+    // exp, ln and powf only exist for the real and complex floating point fields
+    fn exp(&self) -> Self where Self: Sized {
+        panic!("exp not implemented for {}", std::any::type_name::<Self>());
+    }
+
+    fn ln(&self) -> Self where Self: Sized {
+        panic!("ln not implemented for {}", std::any::type_name::<Self>());
+    }
+
+    fn powf(&self, _exponent: &Self) -> Self where Self: Sized {
+        panic!("powf not implemented for {}", std::any::type_name::<Self>());
+    }
+}
@@ -0,0 +1,8 @@
+// a size for pivoting and error estimates, separate from IOrdered: complex numbers and finite
+// fields have a norm but no ordering compatible with the field operations.
+// |x| for the real fields, the modulus for complex numbers, and for finite fields the residue
+// (any non-zero pivot is exact there), so LU picks the largest residue as FiniteLU and the
+// other ports do
+pub trait INormed {
+    fn norm(&self) -> f64;
+}
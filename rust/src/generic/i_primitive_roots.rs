@@ -1,5 +1,12 @@
+use std::sync::Arc;
+
 pub trait IPrimitiveRoots<N> {
     fn primitive_root(&self, p: u64) -> N;
     fn pow(&self, exp: u64) -> N;
     fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<N>;
-}
\ No newline at end of file
+    // the same roots as a table shared between transforms, the fields with an entry in the
+    // roots_of_unity cache override this
+    fn roots_of_unity(&self, n: u32, direction: i32) -> Arc<Vec<N>> {
+        Arc::new(self.precomputeRootsOfUnity(n, direction))
+    }
+}
@@ -0,0 +1,4 @@
+pub trait IRounding {
+    fn next_up(&self) -> Self;
+    fn next_down(&self) -> Self;
+}
\ No newline at end of file
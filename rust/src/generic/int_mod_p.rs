@@ -1,10 +1,15 @@
 use std::fmt;
 use crate::generic::i_field::IField;
-use crate::generic::i_ordered::IOrdered;
+use crate::generic::field_error::FieldError;
+use crate::generic::field_ops::{impl_field_ops, impl_num_traits};
+use crate::generic::i_normed::INormed;
 use crate::generic::i_math::IMath;
 use crate::generic::i_primitive_roots::IPrimitiveRoots;
+use crate::generic::roots_of_unity::{roots_of_unity, powers, root_of_unity_mod};
 use crate::generic::i_copiable::ICopiable;
 use std::hash::Hash;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::collections::HashMap;
 use std::cmp::Eq;
 #[derive(Debug)]
 pub struct IntModP {
@@ -13,7 +18,7 @@ pub struct IntModP {
 
 pub static mut MODULUS: u64 = 7;
 
-fn get_modulus() -> u64 {
+pub fn get_modulus() -> u64 {
     unsafe { MODULUS }
 }
 
@@ -23,14 +28,25 @@ pub fn set_modulus(p: u64) {
     }
 }
 
-fn mod_inverse(a: u64, p: u64) -> u64 {
+// a * b mod p without overflow for any p < 2^63, the u128 path is only taken for moduli above 2^32
+#[inline(always)]
+pub(crate) fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    if p <= 1 << 32 {
+        (a * b) % p
+    } else {
+        ((a as u128 * b as u128) % p as u128) as u64
+    }
+}
+
+pub(crate) fn mod_inverse(a: u64, p: u64) -> u64 {
     if a == 0 {
         panic!("Inverse does not exist for zero, {}, {}", a, p);
     }
     let mut a = a % p;
     let mut m = p;
-    let mut x0: i64 = 0;
-    let mut x1: i64 = 1;
+    // the Bezout coefficients stay below p, but q * x0 can pass i64::MAX for 63-bit moduli
+    let mut x0: i128 = 0;
+    let mut x1: i128 = 1;
     if m == 1 {
         return 0;
     }
@@ -40,15 +56,31 @@ fn mod_inverse(a: u64, p: u64) -> u64 {
         m = a % m;
         a = t;
         let temp = x0;
-        x0 = x1 - q as i64 * x0;
+        x0 = x1 - q as i128 * x0;
         x1 = temp;
     }
     if x1 < 0 {
-        x1 += p as i64;
+        x1 += p as i128;
     }
     x1 as u64
 }
 
+// a^-1 mod p, or why it does not exist; p need not be prime
+pub(crate) fn checked_mod_inverse(a: u64, p: u64) -> Result<u64, FieldError> {
+    let a = a % p;
+    if a == 0 {
+        return Err(FieldError::DivisionByZero);
+    }
+    let (mut x, mut y) = (a, p);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    if x != 1 {
+        return Err(FieldError::NotInvertible);
+    }
+    Ok(mod_inverse(a, p))
+}
+
 impl IntModP {
     pub fn new(i: u64) -> Self {
         let p = get_modulus();
@@ -85,12 +117,12 @@ impl IField for IntModP {
     }
 
     fn m(&self, o: &IntModP) -> IntModP {
-        IntModP::new(self.i * o.i)
+        IntModP { i: mul_mod(self.i, o.i, get_modulus()) }
     }
 
     fn me(&mut self, o: &IntModP) {
         let p = get_modulus();
-        self.i = (self.i * o.i) % p;
+        self.i = mul_mod(self.i, o.i, p);
     }
 
     fn d(&self, o: &IntModP) -> IntModP {
@@ -100,7 +132,7 @@ impl IField for IntModP {
         }
         else {
             let inv = mod_inverse(o.i, p);
-            IntModP::new(self.i * inv)
+            IntModP { i: mul_mod(self.i, inv, p) }
         }
     }
     fn de(&mut self, o: &IntModP) {
@@ -110,16 +142,24 @@ impl IField for IntModP {
         }
         else {
             let inv = mod_inverse(o.i, p);
-            self.i = (self.i * inv) % p;
+            self.i = mul_mod(self.i, inv, p);
         }
     }
 
+    // set_modulus does not check primality, under a composite modulus mod_inverse of a non-unit
+    // runs into a division by zero, so the gcd is checked first
+    fn checked_d(&self, o: &IntModP) -> Result<IntModP, FieldError> {
+        let p = get_modulus();
+        let inv = checked_mod_inverse(o.i, p)?;
+        Ok(IntModP { i: mul_mod(self.i, inv, p) })
+    }
+
     fn coerce_to_f64(&self) -> f64 {
         self.i as f64
     }
 
     fn coerce_from_int(&self, value: i32) -> Self {
-        IntModP::new(value as u64)
+        IntModP::new((value as i64).rem_euclid(get_modulus() as i64) as u64)
     }
 
     fn coerce(&self, value: f64) -> IntModP {
@@ -139,6 +179,26 @@ impl IField for IntModP {
     fn one(&self) -> IntModP {
         IntModP::new(1)
     }
+    fn new_zero() -> IntModP {
+        IntModP::new(0)
+    }
+    fn new_one() -> IntModP {
+        IntModP::new(1)
+    }
+}
+
+impl_field_ops!(IntModP);
+
+impl_num_traits!(IntModP);
+
+// integers in any radix, negative ones included, reduced mod the current modulus
+impl num_traits::Num for IntModP {
+    type FromStrRadixErr = std::num::ParseIntError;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<IntModP, std::num::ParseIntError> {
+        let value = i128::from_str_radix(s, radix)?;
+        Ok(IntModP::new(value.rem_euclid(get_modulus() as i128) as u64))
+    }
 }
 
 impl ICopiable for IntModP{
@@ -153,25 +213,9 @@ impl Clone for IntModP {
     }
 }
 
-impl IOrdered for IntModP {
-    fn lt(&self, o: &IntModP) -> bool {
-        self.i < o.i
-    }
-
-    fn le(&self, o: &IntModP) -> bool {
-        self.i <= o.i
-    }
-
-    fn gt(&self, o: &IntModP) -> bool {
-        self.i > o.i
-    }
-
-    fn ge(&self, o: &IntModP) -> bool {
-        self.i >= o.i
-    }
-
-    fn e(&self, o: &IntModP) -> bool {
-        self.i == o.i
+impl INormed for IntModP {
+    fn norm(&self) -> f64 {
+        self.i as f64
     }
 }
 
@@ -181,7 +225,11 @@ impl IMath for IntModP {
     }
 
     fn sqrt(&mut self) -> IntModP {
-        panic!("Square root not implemented for IntModP");
+        self.try_sqrt().unwrap_or_else(|| panic!("{} is not a square mod {}", self.i, get_modulus()))
+    }
+
+    fn try_sqrt(&self) -> Option<IntModP> {
+        sqrt_mod(self.i, get_modulus()).map(|i| IntModP { i })
     }
 }
 
@@ -192,10 +240,10 @@ impl fmt::Display for IntModP {
 }
 
 // Factorize a number
-fn factorize(mut n: u64) -> Vec<u64> {
+pub(crate) fn factorize(mut n: u64) -> Vec<u64> {
     let mut factors = Vec::new();
     let mut i = 2;
-    while i * i <= n {
+    while i <= n / i {
         if n % i == 0 {
             factors.push(i);
             while n % i == 0 {
@@ -210,8 +258,8 @@ fn factorize(mut n: u64) -> Vec<u64> {
     factors
 }
 
- fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
-        if modulus <= 0 {
+ pub(crate) fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+        if modulus == 0 {
             panic!("Modulus must be positive");
         }
 
@@ -221,9 +269,9 @@ fn factorize(mut n: u64) -> Vec<u64> {
 
         while exp > 0 {
             if exp % 2 == 1 {
-                result = (result * base) % modulus;
+                result = mul_mod(result, base, modulus);
             }
-            base = (base * base) % modulus;
+            base = mul_mod(base, base, modulus);
             exp /= 2;
         }
 
@@ -231,46 +279,131 @@ fn factorize(mut n: u64) -> Vec<u64> {
     }
 
 
-impl IPrimitiveRoots<IntModP> for IntModP {
-    fn primitive_root(&self, n: u64) -> Self {
-        let p = get_modulus();
-        let factors = factorize(p as u64 - 1);
-        for g in 2..p {
-            let mut is_root = true;
-            for &factor in &factors {
-                if mod_pow(g, (p - 1) / factor as u64, p) == 1 {
-                    is_root = false;
-                    break;
-                }
-            }
-            if is_root {
-                return Self::new(g);
+// smallest generator of (Z/p)*, p - 1 is only factored the first time a modulus is seen
+pub fn primitive_root_mod(p: u64) -> u64 {
+    static ROOTS: OnceLock<Mutex<HashMap<u64, u64>>> = OnceLock::new();
+    let roots = ROOTS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(&g) = roots.lock().unwrap().get(&p) {
+        return g;
+    }
+    let factors = factorize(p - 1);
+    let g = (2..p).find(|&g| factors.iter().all(|&factor| mod_pow(g, (p - 1) / factor, p) != 1)).unwrap_or(0);
+    roots.lock().unwrap().insert(p, g);
+    g
+}
+
+// Euler's criterion, p an odd prime
+fn is_quadratic_residue(a: u64, p: u64) -> bool {
+    a.is_multiple_of(p) || mod_pow(a, (p - 1) / 2, p) == 1
+}
+
+// of the two roots r and p - r the smaller one, so every algorithm returns the same value
+fn canonical_root(r: u64, p: u64) -> u64 {
+    if r == 0 { 0 } else { r.min(p - r) }
+}
+
+// Tonelli-Shanks for p - 1 = q * 2^s, O(log p + s^2) multiplications. None for non-residues,
+// and for composite moduli where the loop would not terminate
+pub fn sqrt_mod_tonelli_shanks(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if p == 2 || a == 0 {
+        return Some(a);
+    }
+    if !is_quadratic_residue(a, p) {
+        return None;
+    }
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..p).find(|&z| !is_quadratic_residue(z, p))?;
+    let mut m = s;
+    let mut c = mod_pow(z, q, p);
+    let mut t = mod_pow(a, q, p);
+    let mut r = mod_pow(a, q.div_ceil(2), p);
+    while t != 1 {
+        // least i with t^(2^i) = 1
+        let mut i = 0;
+        let mut t_pow = t;
+        while t_pow != 1 {
+            t_pow = mul_mod(t_pow, t_pow, p);
+            i += 1;
+            if i == m {
+                return None;
             }
         }
-        Self::new(0)
+        let mut b = c;
+        for _ in 0..(m - i - 1) {
+            b = mul_mod(b, b, p);
+        }
+        m = i;
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        r = mul_mod(r, b, p);
+    }
+    Some(canonical_root(r, p))
+}
+
+// Cipolla: with t^2 - a a non-residue, (t + w)^((p + 1) / 2) in GF(p)[w] / (w^2 - (t^2 - a)) is
+// a root of a. O(log p) multiplications in GF(p^2) whatever the power of two in p - 1
+pub fn sqrt_mod_cipolla(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if p == 2 || a == 0 {
+        return Some(a);
+    }
+    if !is_quadratic_residue(a, p) {
+        return None;
+    }
+    let (t, w2) = (1..p)
+        .map(|t| (t, (mul_mod(t, t, p) + p - a) % p))
+        .find(|&(_, w2)| !is_quadratic_residue(w2, p))?;
+    // (x0 + x1 w)(y0 + y1 w) = (x0 y0 + x1 y1 w^2) + (x0 y1 + x1 y0) w
+    let mul = |x: (u64, u64), y: (u64, u64)| {
+        (
+            (mul_mod(x.0, y.0, p) + mul_mod(mul_mod(x.1, y.1, p), w2, p)) % p,
+            (mul_mod(x.0, y.1, p) + mul_mod(x.1, y.0, p)) % p,
+        )
+    };
+    let mut result = (1, 0);
+    let mut base = (t, 1);
+    let mut exp = p.div_ceil(2);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    Some(canonical_root(result.0, p))
+}
+
+// Tonelli-Shanks unless p - 1 has a large power of two (NTT primes), where its s^2 term
+// outgrows Cipolla's fixed cost
+pub fn sqrt_mod(a: u64, p: u64) -> Option<u64> {
+    let s = (p - 1).trailing_zeros() as u64;
+    let bits = 64 - p.leading_zeros() as u64;
+    if s * s > 8 * bits {
+        sqrt_mod_cipolla(a, p)
+    } else {
+        sqrt_mod_tonelli_shanks(a, p)
+    }
+}
+
+impl IPrimitiveRoots<IntModP> for IntModP {
+    fn primitive_root(&self, _n: u64) -> Self {
+        Self::new(primitive_root_mod(get_modulus()))
     }
 
     fn pow(&self, exp: u64) -> IntModP {
         let p = get_modulus();
-        IntModP::new(mod_pow(self.i, exp as u64, p))
+        IntModP::new(mod_pow(self.i, exp, p))
     }
 
     fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<IntModP> {
+        self.roots_of_unity(n, direction).to_vec()
+    }
+
+    fn roots_of_unity(&self, n: u32, direction: i32) -> Arc<Vec<IntModP>> {
         let p = get_modulus();
-        if (p - 1) % n as u64 != 0 {
-            panic!("n must divide p-1 for roots of unity to exist in IntModP");
-        }
-        let g = self.primitive_root(p);
-        let omega = g.pow((p - 1) / (n as u64));
-        let mut roots = Vec::with_capacity(n as usize);
-        for k in 0..n as i32 {
-            let mut exponent: u64 = ((k * direction + (p - 1) as i32) % (p - 1) as i32) as u64;
-            if exponent < 0 {
-                exponent += (p - 1) as u64;
-            }
-            roots.push(omega.pow(exponent));
-        }
-        roots
+        roots_of_unity(p, n, direction, || powers(&IntModP::new(root_of_unity_mod(p, n, direction)), n))
     }
 }
 
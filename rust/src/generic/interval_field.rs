@@ -0,0 +1,279 @@
+use std::fmt;
+use crate::generic::i_copiable::ICopiable;
+use crate::generic::i_field::IField;
+use crate::generic::field_error::FieldError;
+use crate::generic::field_ops::impl_field_ops;
+use crate::generic::i_math::IMath;
+use crate::generic::i_ordered::IOrdered;
+use crate::generic::i_normed::INormed;
+use crate::generic::i_rounding::IRounding;
+use std::hash::Hash;
+use std::cmp::Eq;
+
+// Closed interval [lo, hi] over a floating point field. Every operation is done in T with
+// round-to-nearest and the endpoints are then pushed one ulp outwards, so the exact result of
+// the same computation in the reals is always enclosed
+#[derive(Debug)]
+pub struct IntervalField<T> {
+    pub lo: T,
+    pub hi: T,
+}
+
+impl<T: IField + IOrdered + ICopiable + IRounding> IntervalField<T> {
+    pub fn new(lo: T, hi: T) -> Self {
+        IntervalField { lo, hi }
+    }
+
+    pub fn point(value: T) -> Self {
+        IntervalField { lo: value.copy(), hi: value }
+    }
+
+    // the whole real line, the result of dividing by an interval containing zero
+    pub fn entire(&self) -> Self {
+        IntervalField { lo: self.lo.coerce(f64::NEG_INFINITY), hi: self.lo.coerce(f64::INFINITY) }
+    }
+
+    fn outward(lo: T, hi: T) -> Self {
+        IntervalField { lo: lo.next_down(), hi: hi.next_up() }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.hi.coerce_to_f64() - self.lo.coerce_to_f64()
+    }
+
+    pub fn midpoint(&self) -> f64 {
+        0.5 * (self.lo.coerce_to_f64() + self.hi.coerce_to_f64())
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        self.lo.coerce_to_f64() <= value && value <= self.hi.coerce_to_f64()
+    }
+
+    pub fn contains_zero(&self) -> bool {
+        self.contains(0.0)
+    }
+
+    // hull of the four endpoint results, NaN (0 * inf) means nothing is known
+    fn hull(&self, candidates: [T; 4]) -> Self {
+        if candidates.iter().any(|c| c.coerce_to_f64().is_nan()) {
+            return self.entire();
+        }
+        let mut lo = candidates[0].copy();
+        let mut hi = candidates[0].copy();
+        for c in &candidates[1..] {
+            if c.lt(&lo) {
+                lo = c.copy();
+            }
+            if c.gt(&hi) {
+                hi = c.copy();
+            }
+        }
+        IntervalField::outward(lo, hi)
+    }
+
+    fn negate(&self) -> Self {
+        let zero = self.lo.zero();
+        IntervalField { lo: zero.s(&self.hi), hi: zero.s(&self.lo) }
+    }
+}
+
+impl<T: IField + IOrdered + ICopiable + IRounding> IField for IntervalField<T> {
+    fn a(&self, o: &IntervalField<T>) -> IntervalField<T> {
+        IntervalField::outward(self.lo.a(&o.lo), self.hi.a(&o.hi))
+    }
+
+    fn ae(&mut self, o: &IntervalField<T>) {
+        *self = self.a(o);
+    }
+
+    fn s(&self, o: &IntervalField<T>) -> IntervalField<T> {
+        IntervalField::outward(self.lo.s(&o.hi), self.hi.s(&o.lo))
+    }
+
+    fn se(&mut self, o: &IntervalField<T>) {
+        *self = self.s(o);
+    }
+
+    fn m(&self, o: &IntervalField<T>) -> IntervalField<T> {
+        self.hull([self.lo.m(&o.lo), self.lo.m(&o.hi), self.hi.m(&o.lo), self.hi.m(&o.hi)])
+    }
+
+    fn me(&mut self, o: &IntervalField<T>) {
+        *self = self.m(o);
+    }
+
+    fn d(&self, o: &IntervalField<T>) -> IntervalField<T> {
+        if o.contains_zero() {
+            return self.entire();
+        }
+        self.hull([self.lo.d(&o.lo), self.lo.d(&o.hi), self.hi.d(&o.lo), self.hi.d(&o.hi)])
+    }
+
+    fn de(&mut self, o: &IntervalField<T>) {
+        *self = self.d(o);
+    }
+
+    // d returns entire() for a divisor containing zero, here that is an error
+    fn checked_d(&self, o: &IntervalField<T>) -> Result<IntervalField<T>, FieldError> {
+        if o.contains_zero() {
+            Err(FieldError::DivisionByZero)
+        } else {
+            Ok(self.d(o))
+        }
+    }
+
+    fn coerce_to_f64(&self) -> f64 {
+        self.midpoint()
+    }
+
+    fn coerce_from_int(&self, value: i32) -> Self {
+        self.coerce(value as f64)
+    }
+
+    // exact values stay points, anything T had to round gets widened
+    fn coerce(&self, value: f64) -> IntervalField<T> {
+        let t = self.lo.coerce(value);
+        if t.coerce_to_f64() == value {
+            IntervalField::point(t)
+        } else {
+            IntervalField::outward(t.copy(), t)
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.lo.is_zero() && self.hi.is_zero()
+    }
+
+    fn is_one(&self) -> bool {
+        self.lo.is_one() && self.hi.is_one()
+    }
+
+    fn zero(&self) -> IntervalField<T> {
+        IntervalField::point(self.lo.zero())
+    }
+
+    fn one(&self) -> IntervalField<T> {
+        IntervalField::point(self.lo.one())
+    }
+
+    fn new_zero() -> IntervalField<T> {
+        IntervalField::point(T::new_zero())
+    }
+
+    fn new_one() -> IntervalField<T> {
+        IntervalField::point(T::new_one())
+    }
+}
+
+impl_field_ops!([T: IField + IOrdered + ICopiable + IRounding] IntervalField<T>);
+
+impl<T: ICopiable> ICopiable for IntervalField<T> {
+    fn copy(&self) -> Self {
+        IntervalField { lo: self.lo.copy(), hi: self.hi.copy() }
+    }
+}
+
+impl<T: ICopiable> Clone for IntervalField<T> {
+    fn clone(&self) -> Self {
+        self.copy()
+    }
+}
+
+impl<T: IField + IOrdered + ICopiable + IRounding + IMath> IMath for IntervalField<T> {
+    fn abs(&self) -> IntervalField<T> {
+        let zero = self.lo.zero();
+        if self.lo.ge(&zero) {
+            self.copy()
+        } else if self.hi.le(&zero) {
+            self.negate()
+        } else {
+            let neg_lo = zero.s(&self.lo);
+            let hi = if neg_lo.gt(&self.hi) { neg_lo } else { self.hi.copy() };
+            IntervalField { lo: zero, hi }
+        }
+    }
+
+    fn sqrt(&mut self) -> IntervalField<T> {
+        let zero = self.lo.zero();
+        let mut lo = if self.lo.lt(&zero) { zero.copy() } else { self.lo.copy() };
+        let mut hi = self.hi.copy();
+        let lo_root = lo.sqrt().next_down();
+        let lo_root = if lo_root.lt(&zero) { zero } else { lo_root };
+        IntervalField { lo: lo_root, hi: hi.sqrt().next_up() }
+    }
+
+    fn try_sqrt(&self) -> Option<IntervalField<T>> {
+        if self.hi.lt(&self.hi.zero()) { None } else { Some(self.copy().sqrt()) }
+    }
+
+    // exp and ln are increasing, so the endpoint images pushed out one ulp enclose the range
+    fn exp(&self) -> IntervalField<T> {
+        IntervalField::outward(self.lo.exp(), self.hi.exp())
+    }
+
+    // the part of the interval at or below zero has no logarithm and is cut off at -inf
+    fn ln(&self) -> IntervalField<T> {
+        let zero = self.lo.zero();
+        if self.hi.le(&zero) {
+            return self.entire();
+        }
+        let lo = if self.lo.le(&zero) { self.lo.coerce(f64::NEG_INFINITY) } else { self.lo.ln().next_down() };
+        IntervalField { lo, hi: self.hi.ln().next_up() }
+    }
+
+    fn powf(&self, exponent: &IntervalField<T>) -> IntervalField<T> {
+        exponent.m(&self.ln()).exp()
+    }
+}
+
+// "certainly" comparisons: lt holds only when every point of self is below every point of o.
+// Overlapping intervals are neither lt nor gt, so LU pivoting keeps the earlier row
+impl<T: IOrdered> IOrdered for IntervalField<T> {
+    fn lt(&self, o: &IntervalField<T>) -> bool {
+        self.hi.lt(&o.lo)
+    }
+
+    fn le(&self, o: &IntervalField<T>) -> bool {
+        self.hi.le(&o.lo)
+    }
+
+    fn gt(&self, o: &IntervalField<T>) -> bool {
+        self.lo.gt(&o.hi)
+    }
+
+    fn ge(&self, o: &IntervalField<T>) -> bool {
+        self.lo.ge(&o.hi)
+    }
+
+    fn e(&self, o: &IntervalField<T>) -> bool {
+        self.lo.e(&o.lo) && self.hi.e(&o.hi)
+    }
+}
+
+// the magnitude, the largest |x| over the interval
+impl<T: INormed> INormed for IntervalField<T> {
+    fn norm(&self) -> f64 {
+        self.lo.norm().max(self.hi.norm())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for IntervalField<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+impl<T: Hash> Hash for IntervalField<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.lo.hash(state);
+        self.hi.hash(state);
+    }
+}
+
+impl<T: PartialEq> PartialEq for IntervalField<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.lo == other.lo && self.hi == other.hi
+    }
+}
+
+impl<T: Eq> Eq for IntervalField<T> {}
@@ -9,4 +9,22 @@ pub mod i_primitive_roots;
 pub mod i_exponent;
 pub mod vec_exponent;
 pub mod bit_packed_exponent;
-pub mod i_copiable;
\ No newline at end of file
+pub mod i_copiable;
+pub mod const_int_mod_p;
+pub mod montgomery_int_mod_p;
+pub mod barrett_int_mod_p;
+pub mod rational_field;
+pub mod extension_field;
+pub mod double_double_field;
+pub mod i_rounding;
+pub mod interval_field;
+pub mod dual_field;
+pub mod counting_field;
+pub mod field_ops;
+pub mod field_error;
+pub mod i_normed;
+pub mod roots_of_unity;
+pub mod sparse_exponent;
+pub mod monomial_order;
+pub mod critical_pairs;
+pub mod sparse_matrix;
\ No newline at end of file
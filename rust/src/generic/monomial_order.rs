@@ -0,0 +1,137 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Range;
+use std::sync::Arc;
+use crate::generic::i_exponent::IExponent;
+
+// a term order as a value, so polynomials in different orders can live side by side (a Grobner
+// walk or FGLM holds a basis in two). Orders compare whole exponents, block orders are built
+// from comparisons restricted to a range of variables
+pub trait MonomialOrder: Clone + fmt::Debug + Hash + Eq {
+    // a against b on the variables in vars only
+    fn compare_vars<E: IExponent>(&self, a: &E, b: &E, vars: Range<usize>) -> Ordering;
+
+    fn compare<E: IExponent>(&self, a: &E, b: &E) -> Ordering {
+        self.compare_vars(a, b, 0..a.vars().max(b.vars()))
+    }
+
+    // short name for output, lowercase as Singular and Macaulay2 spell them
+    fn name(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+fn lex_vars<E: IExponent>(a: &E, b: &E, vars: Range<usize>) -> Ordering {
+    vars.map(|v| a.exponent(v).cmp(&b.exponent(v))).find(|o| o.is_ne()).unwrap_or(Ordering::Equal)
+}
+
+fn degree_vars<E: IExponent>(e: &E, vars: Range<usize>) -> u32 {
+    vars.map(|v| e.exponent(v)).sum()
+}
+
+// x0 > x1 > ... compared variable by variable
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Lex;
+
+impl MonomialOrder for Lex {
+    fn compare_vars<E: IExponent>(&self, a: &E, b: &E, vars: Range<usize>) -> Ordering {
+        lex_vars(a, b, vars)
+    }
+
+    fn compare<E: IExponent>(&self, a: &E, b: &E) -> Ordering {
+        a.lex_compare(b)
+    }
+
+    fn name(&self) -> String {
+        "lex".to_string()
+    }
+}
+
+// total degree, ties broken by lex
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GrLex;
+
+impl MonomialOrder for GrLex {
+    fn compare_vars<E: IExponent>(&self, a: &E, b: &E, vars: Range<usize>) -> Ordering {
+        degree_vars(a, vars.clone()).cmp(&degree_vars(b, vars.clone())).then_with(|| lex_vars(a, b, vars))
+    }
+
+    fn compare<E: IExponent>(&self, a: &E, b: &E) -> Ordering {
+        a.degree().cmp(&b.degree()).then_with(|| a.lex_compare(b))
+    }
+
+    fn name(&self) -> String {
+        "grlex".to_string()
+    }
+}
+
+// total degree, ties broken by the last variable where the exponents differ, the smaller
+// exponent there is the larger monomial
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GRevLex;
+
+impl MonomialOrder for GRevLex {
+    fn compare_vars<E: IExponent>(&self, a: &E, b: &E, vars: Range<usize>) -> Ordering {
+        degree_vars(a, vars.clone()).cmp(&degree_vars(b, vars.clone()))
+            .then_with(|| vars.rev().map(|v| b.exponent(v).cmp(&a.exponent(v))).find(|o| o.is_ne()).unwrap_or(Ordering::Equal))
+    }
+
+    fn name(&self) -> String {
+        "grevlex".to_string()
+    }
+}
+
+// weighted degree sum w_i e_i, ties broken by lex. Variables past the end of the weights have
+// weight 1, so all ones is GrLex
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Weighted {
+    pub weights: Arc<[u32]>,
+}
+
+impl Weighted {
+    pub fn new(weights: &[u32]) -> Self {
+        Weighted { weights: weights.into() }
+    }
+
+    fn weighted_degree<E: IExponent>(&self, e: &E, vars: Range<usize>) -> u64 {
+        vars.map(|v| e.exponent(v) as u64 * self.weights.get(v).copied().unwrap_or(1) as u64).sum()
+    }
+}
+
+impl MonomialOrder for Weighted {
+    fn compare_vars<E: IExponent>(&self, a: &E, b: &E, vars: Range<usize>) -> Ordering {
+        self.weighted_degree(a, vars.clone()).cmp(&self.weighted_degree(b, vars.clone())).then_with(|| lex_vars(a, b, vars))
+    }
+
+    fn name(&self) -> String {
+        format!("weighted({})", self.weights.iter().map(|w| w.to_string()).collect::<Vec<_>>().join(", "))
+    }
+}
+
+// the variables before `split` by `first`, ties broken on the rest by `second`. With Lex or
+// GRevLex on the first block this eliminates those variables: the basis elements free of them
+// are a basis of the elimination ideal
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Block<A: MonomialOrder, B: MonomialOrder> {
+    pub split: usize,
+    pub first: A,
+    pub second: B,
+}
+
+impl<A: MonomialOrder, B: MonomialOrder> Block<A, B> {
+    pub fn new(split: usize, first: A, second: B) -> Self {
+        Block { split, first, second }
+    }
+}
+
+impl<A: MonomialOrder, B: MonomialOrder> MonomialOrder for Block<A, B> {
+    fn compare_vars<E: IExponent>(&self, a: &E, b: &E, vars: Range<usize>) -> Ordering {
+        let mid = self.split.clamp(vars.start, vars.end);
+        self.first.compare_vars(a, b, vars.start..mid).then_with(|| self.second.compare_vars(a, b, mid..vars.end))
+    }
+
+    fn name(&self) -> String {
+        format!("block({} on {} variables, {})", self.first.name(), self.split, self.second.name())
+    }
+}
@@ -0,0 +1,227 @@
+use std::fmt;
+use crate::generic::i_field::IField;
+use crate::generic::field_ops::impl_field_ops;
+use crate::generic::i_normed::INormed;
+use crate::generic::i_math::IMath;
+use crate::generic::i_primitive_roots::IPrimitiveRoots;
+use crate::generic::roots_of_unity::{roots_of_unity, powers, root_of_unity_mod};
+use std::sync::Arc;
+use crate::generic::i_copiable::ICopiable;
+use crate::generic::int_mod_p::{mod_inverse, primitive_root_mod, sqrt_mod};
+use std::hash::Hash;
+use std::cmp::Eq;
+
+// Prime field element kept in Montgomery form (v = i * R mod P, R = 2^64).
+// Multiplication is a u128 product followed by REDC instead of a `%`, P must be odd and below 2^63
+#[derive(Debug)]
+pub struct MontgomeryIntModP<const P: u64> {
+    pub v: u64,
+}
+
+impl<const P: u64> MontgomeryIntModP<P> {
+    // -P^-1 mod 2^64, by Newton iteration (each step doubles the number of correct bits)
+    const NEG_INV: u64 = {
+        assert!(P % 2 == 1 && P < (1 << 63), "MontgomeryIntModP needs an odd modulus below 2^63");
+        let mut inv: u64 = P;
+        let mut k = 0;
+        while k < 6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(P.wrapping_mul(inv)));
+            k += 1;
+        }
+        inv.wrapping_neg()
+    };
+    // R^2 mod P and R^3 mod P, used to move values into Montgomery form and to invert
+    const R2: u64 = {
+        let r = ((1u128 << 64) % P as u128) as u64;
+        ((r as u128 * r as u128) % P as u128) as u64
+    };
+    const R3: u64 = {
+        let r = ((1u128 << 64) % P as u128) as u64;
+        ((Self::R2 as u128 * r as u128) % P as u128) as u64
+    };
+
+    pub fn new(i: u64) -> Self {
+        MontgomeryIntModP { v: Self::redc((i % P) as u128 * Self::R2 as u128) }
+    }
+
+    pub fn modulus() -> u64 {
+        P
+    }
+
+    // the ordinary representative in [0, P)
+    pub fn value(&self) -> u64 {
+        Self::redc(self.v as u128)
+    }
+
+    // t * R^-1 mod P, for t < P * R
+    #[inline(always)]
+    fn redc(t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(Self::NEG_INV);
+        let r = ((t + m as u128 * P as u128) >> 64) as u64;
+        if r >= P { r - P } else { r }
+    }
+
+    fn inverse(&self) -> Self {
+        if self.v == 0 {
+            panic!("Division by zero in MontgomeryIntModP, {}", P);
+        }
+        // (iR)^-1 * R^3 * R^-1 = i^-1 * R
+        MontgomeryIntModP { v: Self::redc(mod_inverse(self.v, P) as u128 * Self::R3 as u128) }
+    }
+}
+
+impl<const P: u64> IField for MontgomeryIntModP<P> {
+    fn a(&self, o: &Self) -> Self {
+        let r = self.v + o.v;
+        MontgomeryIntModP { v: if r >= P { r - P } else { r } }
+    }
+
+    fn ae(&mut self, o: &Self) {
+        self.v += o.v;
+        if self.v >= P {
+            self.v -= P;
+        }
+    }
+
+    fn s(&self, o: &Self) -> Self {
+        MontgomeryIntModP { v: if self.v >= o.v { self.v - o.v } else { self.v + P - o.v } }
+    }
+
+    fn se(&mut self, o: &Self) {
+        self.v = if self.v >= o.v { self.v - o.v } else { self.v + P - o.v };
+    }
+
+    fn m(&self, o: &Self) -> Self {
+        MontgomeryIntModP { v: Self::redc(self.v as u128 * o.v as u128) }
+    }
+
+    fn me(&mut self, o: &Self) {
+        self.v = Self::redc(self.v as u128 * o.v as u128);
+    }
+
+    fn d(&self, o: &Self) -> Self {
+        self.m(&o.inverse())
+    }
+
+    fn de(&mut self, o: &Self) {
+        let inv = o.inverse();
+        self.me(&inv);
+    }
+
+    fn coerce_to_f64(&self) -> f64 {
+        self.value() as f64
+    }
+
+    fn coerce_from_int(&self, value: i32) -> Self {
+        MontgomeryIntModP::new((value as i64).rem_euclid(P as i64) as u64)
+    }
+
+    fn coerce(&self, value: f64) -> Self {
+        MontgomeryIntModP::new(value as u64)
+    }
+
+    // zero is zero in Montgomery form, one is not
+    fn is_zero(&self) -> bool {
+        self.v == 0
+    }
+    fn is_one(&self) -> bool {
+        self.value() == 1
+    }
+
+    fn zero(&self) -> Self {
+        MontgomeryIntModP { v: 0 }
+    }
+    fn one(&self) -> Self {
+        MontgomeryIntModP::new(1)
+    }
+    fn new_zero() -> Self {
+        MontgomeryIntModP { v: 0 }
+    }
+    fn new_one() -> Self {
+        MontgomeryIntModP::new(1)
+    }
+}
+
+impl_field_ops!([const P: u64] MontgomeryIntModP<P>);
+
+impl<const P: u64> ICopiable for MontgomeryIntModP<P> {
+    fn copy(&self) -> Self {
+        MontgomeryIntModP { v: self.v }
+    }
+}
+
+impl<const P: u64> Clone for MontgomeryIntModP<P> {
+    fn clone(&self) -> Self {
+        self.copy()
+    }
+}
+
+// the ordinary representative, the Montgomery form of a residue is not its size
+impl<const P: u64> INormed for MontgomeryIntModP<P> {
+    fn norm(&self) -> f64 {
+        self.value() as f64
+    }
+}
+
+impl<const P: u64> IMath for MontgomeryIntModP<P> {
+    fn abs(&self) -> Self {
+        self.copy()
+    }
+
+    fn sqrt(&mut self) -> Self {
+        self.try_sqrt().unwrap_or_else(|| panic!("{} is not a square mod {}", self.value(), P))
+    }
+
+    fn try_sqrt(&self) -> Option<Self> {
+        sqrt_mod(self.value(), P).map(MontgomeryIntModP::new)
+    }
+}
+
+impl<const P: u64> fmt::Display for MontgomeryIntModP<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IntModP({})", self.value())
+    }
+}
+
+impl<const P: u64> IPrimitiveRoots<MontgomeryIntModP<P>> for MontgomeryIntModP<P> {
+    fn primitive_root(&self, _n: u64) -> Self {
+        Self::new(primitive_root_mod(P))
+    }
+
+    // square and multiply without leaving Montgomery form
+    fn pow(&self, exp: u64) -> Self {
+        let mut result = self.one();
+        let mut base = self.copy();
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result.me(&base);
+            }
+            base = base.m(&base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    fn precomputeRootsOfUnity(&self, n: u32, direction: i32) -> Vec<Self> {
+        self.roots_of_unity(n, direction).to_vec()
+    }
+
+    fn roots_of_unity(&self, n: u32, direction: i32) -> Arc<Vec<Self>> {
+        roots_of_unity(P, n, direction, || powers(&Self::new(root_of_unity_mod(P, n, direction)), n))
+    }
+}
+
+impl<const P: u64> Hash for MontgomeryIntModP<P> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.v.hash(state);
+    }
+}
+
+impl<const P: u64> PartialEq for MontgomeryIntModP<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.v == other.v
+    }
+}
+
+impl<const P: u64> Eq for MontgomeryIntModP<P> {}
@@ -0,0 +1,249 @@
+use std::fmt;
+use crate::generic::i_copiable::ICopiable;
+use crate::generic::i_field::IField;
+use crate::generic::field_ops::impl_field_ops;
+use crate::generic::i_math::IMath;
+use crate::generic::i_ordered::IOrdered;
+use crate::generic::i_normed::INormed;
+use num_bigint::BigInt;
+use num_integer::{Integer, Roots};
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::hash::Hash;
+use std::cmp::Eq;
+
+// Exact rational number, always stored in lowest terms with a positive denominator
+// so that equal values have equal fields (needed for Hash/Eq in the Grobner code)
+#[derive(Debug)]
+pub struct RationalField {
+    pub numerator: BigInt,
+    pub denominator: BigInt,
+}
+
+impl RationalField {
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Self {
+        if denominator.is_zero() {
+            panic!("Zero denominator in RationalField");
+        }
+        let gcd = numerator.gcd(&denominator);
+        let (mut numerator, mut denominator) = if gcd.is_one() || gcd.is_zero() {
+            (numerator, denominator)
+        } else {
+            (numerator / &gcd, denominator / &gcd)
+        };
+        if denominator.is_negative() {
+            numerator = -numerator;
+            denominator = -denominator;
+        }
+        RationalField { numerator, denominator }
+    }
+
+    pub fn from_int(value: i64) -> Self {
+        RationalField { numerator: BigInt::from(value), denominator: BigInt::one() }
+    }
+
+    // exact value of a finite f64 (every finite double is a dyadic rational)
+    pub fn from_f64(value: f64) -> Self {
+        if !value.is_finite() {
+            panic!("Cannot represent {} in RationalField", value);
+        }
+        if value == 0.0 {
+            return RationalField::from_int(0);
+        }
+        let bits = value.to_bits();
+        let sign: i64 = if bits >> 63 == 0 { 1 } else { -1 };
+        let exponent = ((bits >> 52) & 0x7FF) as i64;
+        let mantissa = if exponent == 0 {
+            (bits & 0xF_FFFF_FFFF_FFFF) << 1
+        } else {
+            (bits & 0xF_FFFF_FFFF_FFFF) | 0x10_0000_0000_0000
+        };
+        // value = sign * mantissa * 2^(exponent - 1075)
+        let shift = exponent - 1075;
+        let numerator = BigInt::from(sign) * BigInt::from(mantissa);
+        if shift >= 0 {
+            RationalField::new(numerator << shift as usize, BigInt::one())
+        } else {
+            RationalField::new(numerator, BigInt::one() << (-shift) as usize)
+        }
+    }
+}
+
+impl IField for RationalField {
+    fn a(&self, o: &RationalField) -> RationalField {
+        RationalField::new(
+            &self.numerator * &o.denominator + &o.numerator * &self.denominator,
+            &self.denominator * &o.denominator,
+        )
+    }
+
+    fn ae(&mut self, o: &RationalField) {
+        *self = self.a(o);
+    }
+
+    fn s(&self, o: &RationalField) -> RationalField {
+        RationalField::new(
+            &self.numerator * &o.denominator - &o.numerator * &self.denominator,
+            &self.denominator * &o.denominator,
+        )
+    }
+
+    fn se(&mut self, o: &RationalField) {
+        *self = self.s(o);
+    }
+
+    fn m(&self, o: &RationalField) -> RationalField {
+        RationalField::new(&self.numerator * &o.numerator, &self.denominator * &o.denominator)
+    }
+
+    fn me(&mut self, o: &RationalField) {
+        *self = self.m(o);
+    }
+
+    fn d(&self, o: &RationalField) -> RationalField {
+        if o.numerator.is_zero() {
+            panic!("Division by zero in RationalField");
+        }
+        RationalField::new(&self.numerator * &o.denominator, &self.denominator * &o.numerator)
+    }
+
+    fn de(&mut self, o: &RationalField) {
+        *self = self.d(o);
+    }
+
+    // num and den can each be far outside the f64 range while the quotient is not,
+    // so drop the same number of low bits from both before converting
+    fn coerce_to_f64(&self) -> f64 {
+        let bits = std::cmp::max(self.numerator.bits(), self.denominator.bits());
+        if bits < 1000 {
+            return self.numerator.to_f64().unwrap() / self.denominator.to_f64().unwrap();
+        }
+        let shift = (bits - 1000) as usize;
+        let numerator = (&self.numerator >> shift).to_f64().unwrap();
+        let denominator = (&self.denominator >> shift).to_f64().unwrap();
+        numerator / denominator
+    }
+
+    fn coerce_from_int(&self, value: i32) -> Self {
+        RationalField::from_int(value as i64)
+    }
+
+    fn coerce(&self, value: f64) -> RationalField {
+        RationalField::from_f64(value)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    fn is_one(&self) -> bool {
+        self.numerator.is_one() && self.denominator.is_one()
+    }
+
+    fn zero(&self) -> RationalField {
+        RationalField::from_int(0)
+    }
+
+    fn one(&self) -> RationalField {
+        RationalField::from_int(1)
+    }
+
+    fn new_zero() -> RationalField {
+        RationalField::from_int(0)
+    }
+
+    fn new_one() -> RationalField {
+        RationalField::from_int(1)
+    }
+}
+
+impl_field_ops!(RationalField);
+
+impl ICopiable for RationalField {
+    fn copy(&self) -> Self {
+        RationalField { numerator: self.numerator.clone(), denominator: self.denominator.clone() }
+    }
+}
+
+impl Clone for RationalField {
+    fn clone(&self) -> Self {
+        self.copy()
+    }
+}
+
+impl IMath for RationalField {
+    fn abs(&self) -> RationalField {
+        RationalField { numerator: self.numerator.abs(), denominator: self.denominator.clone() }
+    }
+
+    // exact when numerator and denominator are both perfect squares, otherwise the f64 root
+    fn sqrt(&mut self) -> RationalField {
+        if self.numerator.is_negative() {
+            panic!("Square root of a negative number in RationalField");
+        }
+        let num_root = self.numerator.sqrt();
+        let den_root = self.denominator.sqrt();
+        if &num_root * &num_root == self.numerator && &den_root * &den_root == self.denominator {
+            RationalField { numerator: num_root, denominator: den_root }
+        } else {
+            RationalField::from_f64(self.coerce_to_f64().sqrt())
+        }
+    }
+
+    fn try_sqrt(&self) -> Option<RationalField> {
+        if self.numerator.is_negative() { None } else { Some(self.copy().sqrt()) }
+    }
+}
+
+// denominators are positive, so a/b < c/d  <=>  a*d < c*b
+impl IOrdered for RationalField {
+    fn lt(&self, o: &RationalField) -> bool {
+        &self.numerator * &o.denominator < &o.numerator * &self.denominator
+    }
+
+    fn le(&self, o: &RationalField) -> bool {
+        &self.numerator * &o.denominator <= &o.numerator * &self.denominator
+    }
+
+    fn gt(&self, o: &RationalField) -> bool {
+        &self.numerator * &o.denominator > &o.numerator * &self.denominator
+    }
+
+    fn ge(&self, o: &RationalField) -> bool {
+        &self.numerator * &o.denominator >= &o.numerator * &self.denominator
+    }
+
+    fn e(&self, o: &RationalField) -> bool {
+        self.numerator == o.numerator && self.denominator == o.denominator
+    }
+}
+
+impl INormed for RationalField {
+    fn norm(&self) -> f64 {
+        self.coerce_to_f64().abs()
+    }
+}
+
+impl fmt::Display for RationalField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator.is_one() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl Hash for RationalField {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.numerator.hash(state);
+        self.denominator.hash(state);
+    }
+}
+
+impl PartialEq for RationalField {
+    fn eq(&self, other: &Self) -> bool {
+        self.numerator == other.numerator && self.denominator == other.denominator
+    }
+}
+
+impl Eq for RationalField {}
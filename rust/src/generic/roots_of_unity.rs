@@ -0,0 +1,68 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use crate::generic::i_field::IField;
+use crate::generic::i_copiable::ICopiable;
+use crate::generic::int_mod_p::{mod_pow, primitive_root_mod};
+
+// (element type, modulus, n, direction). The complex float fields use modulus 0, the type
+// already tells SingleField and DoubleField roots apart
+type Key = (TypeId, u64, u32, i32);
+
+fn tables() -> &'static Mutex<HashMap<Key, Arc<dyn Any + Send + Sync>>> {
+    static TABLES: OnceLock<Mutex<HashMap<Key, Arc<dyn Any + Send + Sync>>>> = OnceLock::new();
+    TABLES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn cached<N: Send + Sync + 'static>(key: &Key) -> Option<Arc<Vec<N>>> {
+    let table = tables().lock().unwrap().get(key).cloned()?;
+    Some(table.downcast::<Vec<N>>().unwrap())
+}
+
+// w^0, ..., w^(n-1) for the table of (modulus, n, direction), built by `build` the first time
+// it is asked for. The opposite direction holds the same roots, w^-k = w^(n-k), so when that
+// table is cached the new one is a reindexed copy and `build` is not called.
+// The lock is not held while building, two threads may both build a table and the second wins
+pub fn roots_of_unity<N, F>(modulus: u64, n: u32, direction: i32, build: F) -> Arc<Vec<N>>
+where
+    N: Clone + Send + Sync + 'static,
+    F: FnOnce() -> Vec<N>,
+{
+    let key = (TypeId::of::<N>(), modulus, n, direction);
+    if let Some(table) = cached::<N>(&key) {
+        return table;
+    }
+    let table = match cached::<N>(&(key.0, modulus, n, -direction)) {
+        Some(opposite) => (0..n as usize).map(|k| opposite[(n as usize - k) % n as usize].clone()).collect(),
+        None => build(),
+    };
+    let table = Arc::new(table);
+    tables().lock().unwrap().insert(key, table.clone());
+    table
+}
+
+// step^0, ..., step^(n-1) as a running product, exact in the finite fields
+pub fn powers<N: IField + ICopiable>(step: &N, n: u32) -> Vec<N> {
+    let mut roots = Vec::with_capacity(n as usize);
+    let mut w = step.one();
+    for _ in 0..n {
+        let next = w.m(step);
+        roots.push(w);
+        w = next;
+    }
+    roots
+}
+
+// the primitive n-th root of unity mod p raised to `direction`
+pub fn root_of_unity_mod(p: u64, n: u32, direction: i32) -> u64 {
+    if (p - 1) % n as u64 != 0 {
+        panic!("n must divide p-1 for roots of unity to exist mod {}", p);
+    }
+    let omega = mod_pow(primitive_root_mod(p), (p - 1) / n as u64, p);
+    mod_pow(omega, (direction as i64).rem_euclid(n as i64) as u64, p)
+}
+
+// number of cached tables, for reports
+pub fn cached_tables() -> usize {
+    tables().lock().unwrap().len()
+}
@@ -1,8 +1,11 @@
 use std::fmt;
 use crate::generic::i_copiable::ICopiable;
 use crate::generic::i_field::IField;
+use crate::generic::field_ops::{impl_field_ops, impl_num_traits};
 use crate::generic::i_math::IMath;
 use crate::generic::i_ordered::IOrdered;
+use crate::generic::i_normed::INormed;
+use crate::generic::i_rounding::IRounding;
 use std::hash::Hash;
 use std::cmp::Eq;
 #[derive(Debug)]
@@ -93,6 +96,26 @@ impl IField for SingleField {
     fn one(&self) -> SingleField {
         SingleField::new(1.0)
     }
+
+    fn new_zero() -> SingleField {
+        SingleField::new(0.0)
+    }
+
+    fn new_one() -> SingleField {
+        SingleField::new(1.0)
+    }
+}
+
+impl_field_ops!(SingleField);
+
+impl_num_traits!(SingleField);
+
+impl num_traits::Num for SingleField {
+    type FromStrRadixErr = num_traits::ParseFloatError;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<SingleField, num_traits::ParseFloatError> {
+        <f32 as num_traits::Num>::from_str_radix(s, radix).map(SingleField::new)
+    }
 }
 
 impl ICopiable for SingleField {
@@ -114,6 +137,22 @@ impl IMath for SingleField {
     fn sqrt(&mut self) -> SingleField{
         SingleField::new(self.f.sqrt())
     }
+
+    fn try_sqrt(&self) -> Option<SingleField> {
+        if self.f < 0.0 { None } else { Some(SingleField::new(self.f.sqrt())) }
+    }
+
+    fn exp(&self) -> SingleField {
+        SingleField::new(self.f.exp())
+    }
+
+    fn ln(&self) -> SingleField {
+        SingleField::new(self.f.ln())
+    }
+
+    fn powf(&self, exponent: &SingleField) -> SingleField {
+        SingleField::new(self.f.powf(exponent.f))
+    }
 }
 
 impl IOrdered for SingleField {
@@ -138,6 +177,23 @@ impl IOrdered for SingleField {
     }
 }
 
+impl INormed for SingleField {
+    fn norm(&self) -> f64 {
+        self.f.abs() as f64
+    }
+}
+
+
+// one ulp in each direction, enough to enclose a round-to-nearest result
+impl IRounding for SingleField {
+    fn next_up(&self) -> SingleField {
+        SingleField::new(self.f.next_up())
+    }
+
+    fn next_down(&self) -> SingleField {
+        SingleField::new(self.f.next_down())
+    }
+}
 
 impl fmt::Display for SingleField {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
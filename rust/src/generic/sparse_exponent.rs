@@ -0,0 +1,157 @@
+use std::cmp::Ordering;
+use std::fmt;
+use crate::generic::i_exponent::IExponent;
+use std::hash::{Hash, Hasher};
+
+// (variable, exponent) pairs sorted by variable with no zero exponents, for systems with many
+// variables but few per monomial. The degree is kept alongside so degree() is not a sum
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SparseExponent {
+    pub exponents: Vec<(u32, u32)>,
+    degree: u32,
+}
+
+impl SparseExponent {
+    // pairs in any order, a variable given twice has its exponents added
+    pub fn new(mut exponents: Vec<(u32, u32)>) -> Self {
+        exponents.sort_unstable_by_key(|&(var, _)| var);
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(exponents.len());
+        for (var, exp) in exponents {
+            match merged.last_mut() {
+                Some(last) if last.0 == var => last.1 += exp,
+                _ => merged.push((var, exp)),
+            }
+        }
+        merged.retain(|&(_, exp)| exp != 0);
+        Self::from_sorted(merged)
+    }
+
+    // already sorted, merged and free of zeros
+    fn from_sorted(exponents: Vec<(u32, u32)>) -> Self {
+        let degree = exponents.iter().map(|&(_, exp)| exp).sum();
+        SparseExponent { exponents, degree }
+    }
+
+    pub fn from_dense(exponents: &[u32]) -> Self {
+        Self::from_sorted(exponents.iter().enumerate().filter(|&(_, &exp)| exp != 0).map(|(var, &exp)| (var as u32, exp)).collect())
+    }
+
+    pub fn to_dense(&self, vars: usize) -> Vec<u32> {
+        let mut dense = vec![0; vars];
+        for &(var, exp) in &self.exponents {
+            dense[var as usize] = exp;
+        }
+        dense
+    }
+
+    pub fn get(&self, var: u32) -> u32 {
+        match self.exponents.binary_search_by_key(&var, |&(v, _)| v) {
+            Ok(i) => self.exponents[i].1,
+            Err(_) => 0,
+        }
+    }
+
+    // walks both sorted lists, `both` combines the exponents of a variable present in both and
+    // returns None to stop, `only_self` and `only_other` handle a variable present in one
+    fn merge(
+        &self,
+        other: &Self,
+        mut both: impl FnMut(u32, u32) -> Option<u32>,
+        only_self: impl Fn(u32) -> u32,
+        only_other: impl Fn(u32) -> u32,
+    ) -> Option<Self> {
+        let (a, b) = (&self.exponents, &other.exponents);
+        let mut result = Vec::with_capacity(a.len() + b.len());
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            let (var, exp) = if j == b.len() || (i < a.len() && a[i].0 < b[j].0) {
+                i += 1;
+                (a[i - 1].0, only_self(a[i - 1].1))
+            } else if i == a.len() || b[j].0 < a[i].0 {
+                j += 1;
+                (b[j - 1].0, only_other(b[j - 1].1))
+            } else {
+                i += 1;
+                j += 1;
+                (a[i - 1].0, both(a[i - 1].1, b[j - 1].1)?)
+            };
+            if exp != 0 {
+                result.push((var, exp));
+            }
+        }
+        Some(Self::from_sorted(result))
+    }
+}
+
+impl Hash for SparseExponent {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.exponents.hash(state);
+    }
+}
+
+impl IExponent for SparseExponent {
+    fn add(&self, o: &Self) -> Self {
+        self.merge(o, |a, b| Some(a + b), |a| a, |b| b).unwrap()
+    }
+
+    fn checked_add(&self, o: &Self) -> Option<Self> {
+        self.degree.checked_add(o.degree)?;
+        self.merge(o, |a, b| a.checked_add(b), |a| a, |b| b)
+    }
+
+    // o must divide self, as for the other exponents
+    fn sub(&self, o: &Self) -> Self {
+        self.merge(o, |a, b| Some(a - b), |a| a, |_| panic!("subtracting a variable that is not present")).unwrap()
+    }
+
+    fn lcm(&self, other: &Self) -> Self {
+        self.merge(other, |a, b| Some(a.max(b)), |a| a, |b| b).unwrap()
+    }
+
+    fn degree(&self) -> u32 {
+        self.degree
+    }
+
+    // the first variable where the exponents differ decides, a variable missing on one side has
+    // exponent 0 there
+    fn lex_compare(&self, other: &Self) -> Ordering {
+        for (a, b) in self.exponents.iter().zip(&other.exponents) {
+            if a.0 != b.0 {
+                // the smaller variable is only present on its side
+                return b.0.cmp(&a.0);
+            }
+            if a.1 != b.1 {
+                return a.1.cmp(&b.1);
+            }
+        }
+        self.exponents.len().cmp(&other.exponents.len())
+    }
+
+    fn can_reduce(&self, divisor: &Self) -> bool {
+        if divisor.degree > self.degree || divisor.exponents.len() > self.exponents.len() {
+            return false;
+        }
+        let mut mine = self.exponents.iter();
+        divisor.exponents.iter().all(|&(var, exp)| {
+            mine.by_ref().find(|&&(v, _)| v >= var).is_some_and(|&(v, e)| v == var && e >= exp)
+        })
+    }
+
+    fn exponent(&self, var: usize) -> u32 {
+        self.get(var as u32)
+    }
+
+    fn vars(&self) -> usize {
+        self.exponents.last().map_or(0, |&(var, _)| var as usize + 1)
+    }
+}
+
+impl fmt::Display for SparseExponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Degree: {:04X}, Exponents: ", self.degree)?;
+        for (var, exp) in &self.exponents {
+            write!(f, "x{}^{:02X} ", var, exp)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::generic::i_field::IField;
+use crate::generic::field_error::FieldError;
+
+// rows of (column, coefficient) pairs sorted by column with no zero coefficients. For the
+// Macaulay matrices of F4 column 0 is the largest monomial, so a row starts at its leading term
+pub struct SparseMatrix<C> {
+    pub rows: Vec<Vec<(usize, C)>>,
+    pub columns: usize,
+}
+
+impl<C: IField + Clone> SparseMatrix<C> {
+    pub fn new(columns: usize) -> Self {
+        SparseMatrix { rows: Vec::new(), columns }
+    }
+
+    pub fn push_row(&mut self, row: Vec<(usize, C)>) {
+        debug_assert!(row.windows(2).all(|w| w[0].0 < w[1].0) && row.iter().all(|(col, _)| *col < self.columns));
+        self.rows.push(row);
+    }
+
+    pub fn nonzeros(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).sum()
+    }
+
+    // structured elimination: of the rows starting at the same column the sparsest is the pivot
+    // there, the others are reduced by all pivots and whatever survives becomes a pivot at its
+    // new leading column in turn. Returns those survivors, monic, in the order they were found.
+    // Their leading columns are exactly the ones no row of the matrix started at
+    pub fn eliminate(self) -> Result<Vec<Vec<(usize, C)>>, FieldError> {
+        let mut pivots: Vec<Option<Vec<(usize, C)>>> = vec![None; self.columns];
+        let mut rest = Vec::new();
+        let mut rows: Vec<Vec<(usize, C)>> = self.rows.into_iter().filter(|row| !row.is_empty()).collect();
+        rows.sort_by_key(|row| (row[0].0, row.len()));
+        for row in rows {
+            let lead = row[0].0;
+            match pivots[lead] {
+                None => pivots[lead] = Some(monic(row)?),
+                Some(_) => rest.push(row),
+            }
+        }
+
+        let mut found = Vec::new();
+        let mut dense = vec![C::new_zero(); self.columns];
+        for row in rest {
+            let first = row[0].0;
+            for (col, c) in row {
+                dense[col] = c;
+            }
+            let mut reduced = Vec::new();
+            for col in first..self.columns {
+                if dense[col].is_zero() {
+                    continue;
+                }
+                let c = std::mem::replace(&mut dense[col], C::new_zero());
+                match &pivots[col] {
+                    // the pivot is monic, subtracting c times it clears this column and only
+                    // touches the ones after it
+                    Some(pivot) => {
+                        for (pcol, pc) in &pivot[1..] {
+                            dense[*pcol].se(&c.m(pc));
+                        }
+                    }
+                    None => reduced.push((col, c)),
+                }
+            }
+            if let Some(&(lead, _)) = reduced.first() {
+                let reduced = monic(reduced)?;
+                pivots[lead] = Some(reduced.clone());
+                found.push(reduced);
+            }
+        }
+        Ok(found)
+    }
+}
+
+fn monic<C: IField + Clone>(row: Vec<(usize, C)>) -> Result<Vec<(usize, C)>, FieldError> {
+    if row[0].1.is_one() {
+        return Ok(row);
+    }
+    let inverse = row[0].1.try_inv()?;
+    Ok(row.into_iter().map(|(col, c)| (col, c.m(&inverse))).collect())
+}
@@ -28,6 +28,12 @@ impl IExponent for VecExponent {
         VecExponent{ exponents: result }
     }
 
+    fn checked_add(&self, o: &Self) -> Option<Self> {
+        let result = self.exponents.iter().zip(&o.exponents).map(|(a, b)| a.checked_add(*b)).collect::<Option<Vec<u32>>>()?;
+        result.iter().try_fold(0u32, |degree, e| degree.checked_add(*e))?;
+        Some(VecExponent{ exponents: result })
+    }
+
     fn sub(&self, o: &Self) -> Self {
         let result: Vec<u32> = self.exponents.iter().zip(&o.exponents).map(|(a, b)| a - b).collect();
         VecExponent{ exponents: result }
@@ -66,6 +72,14 @@ impl IExponent for VecExponent {
         }
         true
     }
+
+    fn exponent(&self, var: usize) -> u32 {
+        self.exponents.get(var).copied().unwrap_or(0)
+    }
+
+    fn vars(&self) -> usize {
+        self.exponents.len()
+    }
 }
 
 
@@ -1,4 +1,6 @@
-fn cyclic_polynomials(n: usize) -> Vec<String> {
+// cyclic n as text for gen_polynomial_parser, one polynomial per string:
+// x0 + ... + x{n-1}, x0*x1 + x1*x2 + ... + x{n-1}*x0, ..., x0*x1*...*x{n-1} - 1
+pub fn cyclic_polynomials(n: usize) -> Vec<String> {
     let vars: Vec<String> = (0..=n-1).map(|i| format!("x{}", i)).collect();
     let mut polys = Vec::new();
 
@@ -24,10 +26,3 @@ fn cyclic_polynomials(n: usize) -> Vec<String> {
 
     polys
 }
-
-fn main() {
-    let system = cyclic_polynomials(3);
-    for (i, poly) in system.iter().enumerate() {
-        println!("f{} = {}", i+1, poly);
-    }
-}
\ No newline at end of file
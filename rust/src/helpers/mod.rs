@@ -1,3 +1,4 @@
 pub mod lcg;
 pub mod prime_sieve;
-pub mod find_prime;
\ No newline at end of file
+pub mod find_prime;
+pub mod cyclic_polynomials;
\ No newline at end of file
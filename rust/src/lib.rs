@@ -4,6 +4,9 @@ pub mod specialized;
 
 #[path = "GenFFT.rs"] pub mod gen_fft;
 #[path = "GenGrobner.rs"] pub mod gen_grobner;
+#[path = "GenSignatureGrobner.rs"] pub mod gen_signature_grobner;
+#[path = "GenPolynomialParser.rs"] pub mod gen_polynomial_parser;
+#[path = "GenPolynomialFormat.rs"] pub mod gen_polynomial_format;
 #[path = "GenLU.rs"] pub mod gen_lu;
 #[path = "GenMonteCarlo.rs"] pub mod gen_monte_carlo;
 #[path = "GenSOR.rs"] pub mod gen_sor;
@@ -1,11 +1,17 @@
 use crate::helpers::lcg::Lcg;
 use crate::helpers::prime_sieve::prime_sieve;
 use crate::helpers::find_prime::{self, find_prime_congruent_one_mod_n};
+use crate::generic::roots_of_unity::{roots_of_unity, root_of_unity_mod};
+use std::sync::Arc;
 
 static mut MODULUS: i32 = 0;
 
 pub struct FFT {}
 
+pub fn set_modulus(p: i32) {
+    unsafe { MODULUS = p; }
+}
+
 
 fn mod_inverse(a: i32, m: i32) -> i32 {
     let mut m = m;
@@ -25,75 +31,22 @@ fn mod_inverse(a: i32, m: i32) -> i32 {
     x1
 }
 
-fn modpow(mut base: i32, mut exp: i32) -> i32 {
-    let modulus = unsafe {MODULUS};
-    if modulus == 0 { panic!("Modulus must be positive"); }
-    let mut result = 1;
-    base %= modulus;
-    while exp > 0 {
-        if exp % 2 == 1 {
-            result = (result * base) % modulus;
-        }
-        base = (base * base) % modulus;
-        exp /= 2;
-    }
-    result
-}
-
-fn primitive_root() -> i32 {
-    let modulus = unsafe {MODULUS};
-    fn factorize(mut n: i32) -> Vec<i32> {
-        let mut factors = Vec::new();
-        let mut i = 2;
-        while i * i <= n {
-            if n % i == 0 {
-                factors.push(i);
-                while n % i == 0 {
-                    n /= i;
-                }
-            }
-            i += 1;
-        }
-        if n > 1 {
-            factors.push(n);
-        }
-        factors
-    }
-    let p = modulus;
-    let factors = factorize (p - 1);
-    for g in 2..p {
-        let mut is_root = true;
-        for &factor in &factors {
-            if modpow(g, (p - 1) / factor) == 1 {
-                is_root = false;
-                break;
-            }
-        }
-        if is_root {
-            return g;
-        }
-    }
-    0
-
-}
-
-fn precomputeRootsOfUnity(n: i32, direction: i32) -> Vec<i32> {
+// served from the same cache as the generic fields, the running product stays below p^2 < 2^62
+fn precomputeRootsOfUnity(n: i32, direction: i32) -> Arc<Vec<i32>> {
     let modulus = unsafe {MODULUS};
     if (modulus - 1) % n != 0 {
         panic!("n must divide p-1 for roots of unity to exist in IntModP");
     }
-    let g = primitive_root();
-    //println!("Primitive root: {}", g);
-    let omega = modpow(g, (modulus-1)/n);
-    let mut roots = Vec::with_capacity(n as usize);
-    for k in 0..n {
-        let mut exponent = (k * direction % (modulus - 1));
-        if exponent < 0 {
-            exponent += (modulus - 1);
+    roots_of_unity(modulus as u64, n as u32, direction, || {
+        let step = root_of_unity_mod(modulus as u64, n as u32, direction) as i64;
+        let mut roots = Vec::with_capacity(n as usize);
+        let mut w: i64 = 1;
+        for _ in 0..n {
+            roots.push(w as i32);
+            w = w * step % modulus as i64;
         }
-        roots.push(modpow(omega, exponent));
-    }
-    roots
+        roots
+    })
 }
 
 impl FFT
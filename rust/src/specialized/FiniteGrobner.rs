@@ -1,27 +1,50 @@
 use std::collections::HashSet;
 
 use std::hash::{Hash, Hasher};
+use std::cmp::Ordering;
+use std::ops::Range;
+use crate::generic::vec_exponent::VecExponent;
+use crate::generic::i_exponent::IExponent;
+use crate::generic::monomial_order::MonomialOrder;
+use crate::generic::critical_pairs::{PairQueue, PairStats, PairStrategy};
 use std::vec;
 
-static mut TERM_ORDER: TermOrder = TermOrder::Lex; // default to lex order, can be set to GrLex or RevLex as well
 static mut MODULUS: u64 = 7;
 use crate::helpers::lcg::Lcg;
+use crate::generic::int_mod_p::{set_modulus, IntModP};
+use crate::gen_polynomial_parser::driver_system;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Term {
     pub coefficient: u64,
     pub exponents: Vec<usize>, // Exponents for each variable
 }
-#[derive(Copy, Clone, Debug, PartialEq)]
+// the orders this specialized copy takes, matched on in Term::compare over the plain exponent
+// vectors. Weighted and block orders only run through gen_grobner
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum TermOrder {
     Lex,
     GrLex,
     RevLex
 }
 
+// the orders of Term::compare on any exponent, for the PairQueue
+impl MonomialOrder for TermOrder {
+    fn compare_vars<E: IExponent>(&self, a: &E, b: &E, vars: Range<usize>) -> Ordering {
+        let degree = |e: &E| vars.clone().map(|v| e.exponent(v)).sum::<u32>();
+        let lex_vars = |vars: Range<usize>| vars.map(|v| a.exponent(v).cmp(&b.exponent(v))).find(|o| o.is_ne()).unwrap_or(Ordering::Equal);
+        match self {
+            TermOrder::Lex => lex_vars(vars),
+            TermOrder::GrLex => degree(a).cmp(&degree(b)).then_with(|| lex_vars(vars)),
+            // lex from the last variable, as Term::compare
+            TermOrder::RevLex => degree(a).cmp(&degree(b))
+                .then_with(|| vars.clone().rev().map(|v| a.exponent(v).cmp(&b.exponent(v))).find(|o| o.is_ne()).unwrap_or(Ordering::Equal)),
+        }
+    }
+}
+
 impl Term {
-    pub fn compare(&self, other: &Term) -> std::cmp::Ordering {
-        let order = unsafe { TERM_ORDER };
+    pub fn compare(&self, other: &Term, order: TermOrder) -> std::cmp::Ordering {
         match order {
             TermOrder::Lex => self.exponents.cmp(&other.exponents), // Lexicographic order
             TermOrder::GrLex => { // Graded lexicographic order
@@ -49,9 +72,10 @@ impl Hash for Term {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct Polynomial {
     pub terms: Vec<Term>,
+    pub order: TermOrder,
 }
 
 impl Eq for Polynomial {
@@ -64,6 +88,13 @@ impl Hash for Polynomial {
     }
 }
 
+// the terms only, printed bases read as in the other ports whatever the order
+impl std::fmt::Debug for Polynomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Polynomial").field("terms", &self.terms).finish()
+    }
+}
+
 fn mod_inverse(a: u64, m: u64) -> u64 {
     let (mut a, mut m) = (a as i64, m as i64);
     let (mut x0, mut x1) = (0i64, 1i64);
@@ -86,8 +117,13 @@ fn mod_inverse(a: u64, m: u64) -> u64 {
     (x1 % m0) as u64
 }
 impl Polynomial {
-    pub fn new(mut terms: Vec<Term>) -> Self {
-        terms.sort_by(|a, b| b.compare(a));
+    // in lex order, which all the drivers use
+    pub fn new(terms: Vec<Term>) -> Self {
+        Self::with_order(terms, TermOrder::Lex)
+    }
+
+    pub fn with_order(mut terms: Vec<Term>, order: TermOrder) -> Self {
+        terms.sort_by(|a, b| b.compare(a, order));
         terms.retain(|t| t.coefficient != 0); // Remove zero coefficient terms
         // remove terms that are very close but not equal to 0 to handle floating point errors
         //terms.retain(|t| (t.coefficient - 0.0).abs() > 1e-2);
@@ -95,7 +131,7 @@ impl Polynomial {
         /*for term in &mut terms {
             term.coefficient = (term.coefficient * 1e5).round() / 1e5;
         } */  
-        Polynomial { terms }
+        Polynomial { terms, order }
     }
     
 
@@ -121,7 +157,7 @@ impl Polynomial {
         }*/
 
 
-        Polynomial::new(result)
+        Polynomial::with_order(result, self.order)
     }
 
     pub fn subtract(&self, other: &Polynomial) -> Polynomial {
@@ -147,7 +183,7 @@ impl Polynomial {
             println!("Term: {:?}, Coefficient: {}", term.exponents, term.coefficient);
         }*/
 
-        Polynomial::new(result)
+        Polynomial::with_order(result, self.order)
     }
 
     pub fn make_monic(&self) -> Polynomial {
@@ -159,7 +195,7 @@ impl Polynomial {
             coefficient: (t.coefficient * inv) % modulus,
             exponents: t.exponents.clone(),
         }).collect();
-        Polynomial::new(new_terms)
+        Polynomial::with_order(new_terms, self.order)
     }
     
 
@@ -227,7 +263,7 @@ impl Polynomial {
         
     
         result.terms.append(&mut remainder);
-        Polynomial::new(result.terms)
+        Polynomial::with_order(result.terms, self.order)
     }
 
     pub fn multiply_by_term(&self, term: &Term) -> Polynomial {
@@ -246,7 +282,7 @@ impl Polynomial {
             })
             .collect();
 
-        Polynomial::new(terms)
+        Polynomial::with_order(terms, self.order)
     }
 
     pub fn s_polynomial(p1: &Polynomial, p2: &Polynomial) -> Polynomial {
@@ -268,17 +304,19 @@ impl Polynomial {
         //let a_inv = mod_inverse(a, modulus);
 
         // S = (b * x^shift_p1 * p1 - a * x^shift_p2 * p2) mod modulus
-        let scaled_p1 = Polynomial::new(
+        let scaled_p1 = Polynomial::with_order(
             p1.terms.iter().map(|term| Term {
                 coefficient: (b * term.coefficient) % modulus,
                 exponents: term.exponents.iter().zip(&shift_p1).map(|(exp, shift)| exp + shift).collect(),
-            }).collect()
+            }).collect(),
+            p1.order,
         );
-        let scaled_p2 = Polynomial::new(
+        let scaled_p2 = Polynomial::with_order(
             p2.terms.iter().map(|term| Term {
                 coefficient: (a * term.coefficient) % modulus,
                 exponents: term.exponents.iter().zip(&shift_p2).map(|(exp, shift)| exp + shift).collect(),
-            }).collect()
+            }).collect(),
+            p2.order,
         );
         scaled_p1.subtract(&scaled_p2)
     }
@@ -349,6 +387,44 @@ pub fn naive_grobner_basis(polynomials: Vec<Polynomial>) -> Vec<Polynomial> {
     
 }
 
+// Buchberger with the S-pairs in the generic PairQueue over the leading monomials, as
+// gen_grobner::buchberger_grobner_basis. Fifo without criteria reduces every pair
+pub fn buchberger_grobner_basis(polynomials: Vec<Polynomial>, strategy: PairStrategy, criteria: bool) -> (Vec<Polynomial>, PairStats) {
+    let lead = |poly: &Polynomial| VecExponent::new(poly.terms[0].exponents.iter().map(|&e| e as u32).collect());
+    let mut basis: Vec<Polynomial> = polynomials.into_iter().filter(|poly| !poly.terms.is_empty()).collect();
+    let Some(order) = basis.first().map(|poly| poly.order) else {
+        return (Vec::new(), PairStats::default());
+    };
+    let mut queue = PairQueue::new(strategy, criteria, order);
+    for poly in &basis {
+        let sugar = poly.terms.iter().map(|t| t.exponents.iter().sum::<usize>() as u32).max().unwrap_or(0);
+        queue.insert(lead(poly), sugar);
+    }
+
+    while let Some((i, j, sugar)) = queue.pop() {
+        let reduced = Polynomial::s_polynomial(&basis[i], &basis[j]).reduce(&basis);
+        queue.stats.reduced += 1;
+        if reduced.terms.is_empty() {
+            queue.stats.zero += 1;
+            continue;
+        }
+        queue.insert(lead(&reduced), sugar);
+        basis.push(reduced);
+    }
+
+    // a minimal basis from the active elements, each reduced by the others
+    let leads: Vec<VecExponent> = basis.iter().map(lead).collect();
+    let minimal: Vec<usize> = (0..basis.len()).filter(|&k| queue.is_active(k) && !(0..basis.len()).any(|m| {
+        m != k && queue.is_active(m) && leads[k].can_reduce(&leads[m]) && (m < k || leads[k] != leads[m])
+    })).collect();
+    let mut reduced_basis = Vec::with_capacity(minimal.len());
+    for &k in &minimal {
+        let others: Vec<Polynomial> = minimal.iter().filter(|&&m| m != k).map(|&m| basis[m].clone()).collect();
+        reduced_basis.push(basis[k].reduce(&others).make_monic());
+    }
+    (reduced_basis, queue.stats)
+}
+
 pub fn are_bases_equivalent(set_a: Vec<Polynomial>, set_b: Vec<Polynomial>) -> bool {
     // Check if all polynomials in set_a reduce to zero using set_b
     let mut all_ok = true;
@@ -392,12 +468,12 @@ fn main() {
         let num_polynomials: usize = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(3);
         let term_order: usize = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(0);
 
-        match term_order {
-            0 => unsafe { TERM_ORDER = TermOrder::Lex; },
-            1 => unsafe { TERM_ORDER = TermOrder::GrLex; },
-            2 => unsafe { TERM_ORDER = TermOrder::RevLex; },
-            _ => unsafe { TERM_ORDER = TermOrder::Lex; },
-        }
+        let order = match term_order {
+            0 => TermOrder::Lex,
+            1 => TermOrder::GrLex,
+            2 => TermOrder::RevLex,
+            _ => TermOrder::Lex,
+        };
 
         let mut input_basis = Vec::new();
         let modulus = 13;
@@ -412,7 +488,7 @@ fn main() {
                     exponents,
                 });
             }
-            input_basis.push(Polynomial::new(terms));
+            input_basis.push(Polynomial::with_order(terms, order));
         }
 
         let basis = naive_grobner_basis(input_basis);
@@ -426,249 +502,31 @@ fn main() {
     else {
         let args: Vec<String> = std::env::args().collect();
         let n = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(4);
-        let modulus = 7;
-        //Lex, GrLex, RevLex
-        unsafe { TERM_ORDER = TermOrder::Lex; }
-        if ( n == 4) {
-             // cyclic 4
-             println!("Rust specialized finite coeff vec exponent cyclic 4");
-            // a + b + c + d
-            let q1 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 1] },
-            ]);
-            // ab + bc + cd + ad
-            let q2 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 1] },
-            ]);
-            // abc + bcd + cda + dab
-            let q3 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 1] },
-            ]);
-            // abcd - 1
-            let q4 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1] },
-                Term { coefficient: modulus - 1, exponents: vec![0, 0, 0, 0] },
-            ]);
-            let start = vec![q1,q2,q3,q4];
-            for i in 0..10 {
-                let basis = naive_grobner_basis(start.clone());
-                println!("Iteration {}: complete", i);
-                if i == 9 {
-                    println!("Final Grobner Basis:");
-                    for poly in basis {
-                        println!("{:?}\n", poly);
-                    }
-                }
-            }
-     
-        }
-        else if (n == 5){ 
-            // Cyclic 5
-             println!("Rust specialized finite coeff vec exponent cyclic 5");
-            // f1 = x0 + x1 + x2 + x3 + x4
-            let p1 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 0, 1] },
-            ]);
-
-            // f2 = x0x1 + x1x2 + x2x3 + x3x4 + x4x0
-            let p2 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 0, 1] },
-            ]);
-
-            // f3 = x0x1x2 + x1x2x3 + x2x3x4 + x3x4x0 + x4x0x1
-            let p3 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 0, 1] },
-            ]);
-
-            // f4 = x0x1x2x3 + x1x2x3x4 + x2x3x4x0 + x3x4x0x1 + x4x0x1x2 - 1
-            let p4 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 0, 1] },
-            ]);
-
-            // f4 = x0*x1*x2*x3*x4 -1
-            let p5 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 1] },
-                Term { coefficient: modulus-1, exponents: vec![0, 0, 0, 0, 0] },
-            ]);
-            let start = vec![p1,p2,p3,p4,p5];
-            for i in 0..10 {
-                let basis = naive_grobner_basis(start.clone());
-                println!("Iteration {}: complete", i);
-                if i == 9 {
-                    println!("Final Grobner Basis:");
-                    for poly in basis {
-                        println!("{:?}\n", poly);
-                    }
-                }
+        // arg1: n or cyclic:<n> for cyclic n, otherwise a system file, - for stdin. A file named
+        //       like a number needs its path, ./5
+        set_modulus(unsafe { MODULUS });
+        let (name, system) = match driver_system::<IntModP>(args.get(1).map(String::as_str), n) {
+            Ok(system) => system,
+            Err(e) => {
+                println!("{}", e);
+                return;
             }
-     
-            
-        }
-        else if (n == 6){
-            // Cyclic 6
-            println!("Rust specialized finite coeff vec exponent cyclic 6");
-            // f1 = x0 + x1 + x2 + x3 + x4 + x5
-            let p1 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 0, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 0, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 0, 0, 1] },
-            ]);
-            // f2 = x0x1 + x1x2 + x2x3 + x3x4 + x4x5 + x5x0
-            let p2 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 0, 0, 1] },
-            ]);
-            // f3 = x0x1x2 + x1x2x3 + x2x3x4 + x3x4x5 + x4x5x0 + x5x0x1
-            let p3 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 0, 0, 1] },
-            ]);
-            // f4 = x0x1x2x3 + x1x2x3x4 + x2x3x4x5 + x3x4x5x0 + x4x5x0x1 + x5x0x1x2
-            let p4 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 0, 0, 1] },
-            ]);
-            // f5 = x0x1x2x3x4 + x1x2x3x4x5 + x2x3x4x5x0 + x3x4x5x0x1 + x4x5x0x1x2 + x5x0x1x2x3
-            let p5 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 1, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 0, 1] },
-            ]);
-            // f6 = x0x1x2x3x4x5 - 1
-            let p6 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 1, 1] },
-                Term { coefficient: modulus - 1, exponents: vec![0, 0, 0, 0, 0, 0] },
-            ]);
-            let start = vec![p1,p2,p3,p4,p5,p6];
-            for i in 0..10 {
-                let basis = naive_grobner_basis(start.clone());
-                println!("Iteration {}: complete", i);
-                if i == 9 {
-                    println!("Final Grobner Basis:");
-                    for poly in basis {
-                        println!("{:?}\n", poly);
-                    }
+        };
+        println!("Rust specialized finite coeff vec exponent {}", name);
+        let start: Vec<Polynomial> = system.polynomials.iter().map(|terms| Polynomial::new(terms.iter().map(|(exps, c)| Term {
+            coefficient: c.i,
+            exponents: exps.iter().map(|&e| e as usize).collect(),
+        }).collect())).collect();
+        for i in 0..10 {
+            let basis = naive_grobner_basis(start.clone());
+            println!("Iteration {}: complete", i);
+            if i == 9 {
+                println!("Final Grobner Basis:");
+                for poly in basis {
+                    println!("{:?}\n", poly);
                 }
             }
-     
-        }
-        else if (n == 7){
-            // Cyclic 7
-            let p1 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 0, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 0, 0, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 0, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 1, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 0, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 0, 0, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 0, 0, 0, 1] },
-            ]);
-            let p2 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 0, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 0, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 1, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 1, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 0, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 0, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 0, 0, 0, 1] },
-            ]);
-            let p3 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 0, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 1, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 1, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 1, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 0, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 0, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 0, 0, 0, 1] },
-            ]);
-            let p4 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 0, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 1, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 1, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 0, 1, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 0, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 0, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 0, 0, 0, 1] },
-            ]);
-            let p5 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 1, 0, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 1, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 0, 1, 1, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 0, 1, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 0, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 0, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 0, 0, 1] },
-            ]);
-            let p6 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 1, 1, 0] },
-                Term { coefficient: 1, exponents: vec![0, 1, 1, 1, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 0, 1, 1, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 0, 1, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 0, 1, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 0, 1, 1] },
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 1, 0, 1] },
-            ]);
-            let p7 = Polynomial::new(vec![
-                Term { coefficient: 1, exponents: vec![1, 1, 1, 1, 1, 1, 1] },
-                Term { coefficient: modulus - 1, exponents: vec![0, 0, 0, 0, 0, 0, 0] },
-            ]);
-            let start = vec![p1,p2,p3,p4,p5,p6,p7];
-            for i in 0..10 {
-                let basis = naive_grobner_basis(start.clone());
-                println!("Iteration {}: complete", i);
-                if i == 9 {
-                    println!("Final Grobner Basis:");
-                    for poly in basis {
-                        println!("{:?}\n", poly);
-                    }
-                }
-            }
-     
         }
-        else {println!("Invalid test number");}
         
 
 
@@ -2,6 +2,11 @@ use crate::helpers::lcg::Lcg;
 
 static mut MODULUS: i32 = 2^31 -1;
 
+// for running factor from another driver, main sets its own
+pub fn set_modulus(modulus: i32) {
+    unsafe { MODULUS = modulus };
+}
+
 fn mod_inverse(a: i32, m: i32) -> i32 {
     if a == 0 { panic! ("Inverse does not exist for zero"); }
     let mut m = m;
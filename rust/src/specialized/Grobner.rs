@@ -1,7 +1,11 @@
 use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
-use std::sync::OnceLock;
-static TERM_ORDER: OnceLock<TermOrder> = OnceLock::new();
+use std::cmp::Ordering;
+use std::ops::Range;
+use crate::generic::vec_exponent::VecExponent;
+use crate::generic::i_exponent::IExponent;
+use crate::generic::monomial_order::MonomialOrder;
+use crate::generic::critical_pairs::{PairQueue, PairStats, PairStrategy};
 use crate::helpers::lcg::Lcg;
 use num_integer::Integer;
 use num_bigint::BigInt;
@@ -14,16 +18,31 @@ pub struct Term {
     pub exponents: Vec<usize>, // Exponents for each variable
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+// the three orders Term::compare matches on; weighted and block orders are gen_grobner's only
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum TermOrder {
     Lex,
     GrLex,
     RevLex
 }
 
+// the orders of Term::compare on any exponent, for the PairQueue
+impl MonomialOrder for TermOrder {
+    fn compare_vars<E: IExponent>(&self, a: &E, b: &E, vars: Range<usize>) -> Ordering {
+        let degree = |e: &E| vars.clone().map(|v| e.exponent(v)).sum::<u32>();
+        let lex_vars = |vars: Range<usize>| vars.map(|v| a.exponent(v).cmp(&b.exponent(v))).find(|o| o.is_ne()).unwrap_or(Ordering::Equal);
+        match self {
+            TermOrder::Lex => lex_vars(vars),
+            TermOrder::GrLex => degree(a).cmp(&degree(b)).then_with(|| lex_vars(vars)),
+            // lex from the last variable, as Term::compare
+            TermOrder::RevLex => degree(a).cmp(&degree(b))
+                .then_with(|| vars.clone().rev().map(|v| a.exponent(v).cmp(&b.exponent(v))).find(|o| o.is_ne()).unwrap_or(Ordering::Equal)),
+        }
+    }
+}
+
 impl Term {
-    pub fn compare(&self, other: &Term) -> std::cmp::Ordering {
-        let order = TERM_ORDER.get().expect("TERM_ORDER not initialized");
+    pub fn compare(&self, other: &Term, order: TermOrder) -> std::cmp::Ordering {
         match order {
             TermOrder::Lex => self.exponents.cmp(&other.exponents), // Lexicographic order
             TermOrder::GrLex => { // Graded lexicographic order
@@ -53,9 +72,10 @@ impl Hash for Term {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct Polynomial {
     pub terms: Vec<Term>,
+    pub order: TermOrder,
 }
 
 impl Eq for Polynomial {
@@ -68,6 +88,13 @@ impl Hash for Polynomial {
     }
 }
 
+// the terms only, printed bases read as in the other ports whatever the order
+impl std::fmt::Debug for Polynomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Polynomial").field("terms", &self.terms).finish()
+    }
+}
+
 impl Polynomial {
     // Reduce each term as much as possible (normalize numerator/denominator by GCD, denominator always positive)
     fn reduce_terms(terms: &mut Vec<Term>) {
@@ -84,11 +111,16 @@ impl Polynomial {
         }
     }
 
-    pub fn new(mut terms: Vec<Term>) -> Self {
-        terms.sort_by(|a, b| b.compare(a));
+    // in lex order, which all the drivers use
+    pub fn new(terms: Vec<Term>) -> Self {
+        Self::with_order(terms, TermOrder::Lex)
+    }
+
+    pub fn with_order(mut terms: Vec<Term>, order: TermOrder) -> Self {
+        terms.sort_by(|a, b| b.compare(a, order));
         terms.retain(|t| !t.numerator.is_zero());
         Self::reduce_terms(&mut terms);
-        Polynomial { terms }
+        Polynomial { terms, order }
     }
 
     pub fn add(&self, other: &Polynomial) -> Polynomial {
@@ -110,7 +142,7 @@ impl Polynomial {
                 result.push(term.clone());
             }
         }
-        let mut result_poly = Polynomial::new(result);
+        let mut result_poly = Polynomial::with_order(result, self.order);
         Self::reduce_terms(&mut result_poly.terms);
         result_poly
     }
@@ -136,7 +168,7 @@ impl Polynomial {
                 result.push(neg_term);
             }
         }
-        let mut result_poly = Polynomial::new(result);
+        let mut result_poly = Polynomial::with_order(result, self.order);
         Self::reduce_terms(&mut result_poly.terms);
         result_poly
     }
@@ -188,7 +220,7 @@ impl Polynomial {
             denominator: &t.denominator * &term.denominator,
             exponents: t.exponents.iter().zip(&term.exponents).map(|(a, b)| a + b).collect(),
         }).collect();
-        let mut result_poly = Polynomial::new(terms);
+        let mut result_poly = Polynomial::with_order(terms, self.order);
         Self::reduce_terms(&mut result_poly.terms);
         result_poly
     }
@@ -211,20 +243,22 @@ impl Polynomial {
         let scale_p1 = &lcm_num / &(a_num * b_den);
         let scale_p2 = &lcm_num / &(b_num * a_den);
         let scale_factor_p1 = lcm_exponents.iter().zip(&p1.terms[0].exponents).map(|(lcm, exp)| lcm - exp).collect::<Vec<_>>();
-        let scaled_p1 = Polynomial::new(
+        let scaled_p1 = Polynomial::with_order(
             p1.terms.iter().map(|term| Term {
                 numerator: &term.numerator * &scale_p1,
                 denominator: &term.denominator * &scale_p1,
                 exponents: term.exponents.iter().zip(&scale_factor_p1).map(|(exp, scale)| exp + scale).collect(),
-            }).collect()
+            }).collect(),
+            p1.order,
         );
         let scale_factor_p2 = lcm_exponents.iter().zip(&p2.terms[0].exponents).map(|(lcm, exp)| lcm - exp).collect::<Vec<_>>();
-        let scaled_p2 = Polynomial::new(
+        let scaled_p2 = Polynomial::with_order(
             p2.terms.iter().map(|term| Term {
                 numerator: &term.numerator * &scale_p2,
                 denominator: &term.denominator * &scale_p2,
                 exponents: term.exponents.iter().zip(&scale_factor_p2).map(|(exp, scale)| exp + scale).collect(),
-            }).collect()
+            }).collect(),
+            p2.order,
         );
         scaled_p1.subtract(&scaled_p2)
     }
@@ -303,6 +337,44 @@ pub fn naive_grobner_basis(polynomials: Vec<Polynomial>) -> Vec<Polynomial> {
     reduced_basis
 }
 
+// Buchberger with the S-pairs in the generic PairQueue over the leading monomials, as
+// gen_grobner::buchberger_grobner_basis. Fifo without criteria reduces every pair
+pub fn buchberger_grobner_basis(polynomials: Vec<Polynomial>, strategy: PairStrategy, criteria: bool) -> (Vec<Polynomial>, PairStats) {
+    let lead = |poly: &Polynomial| VecExponent::new(poly.terms[0].exponents.iter().map(|&e| e as u32).collect());
+    let mut basis: Vec<Polynomial> = polynomials.into_iter().filter(|poly| !poly.terms.is_empty()).collect();
+    let Some(order) = basis.first().map(|poly| poly.order) else {
+        return (Vec::new(), PairStats::default());
+    };
+    let mut queue = PairQueue::new(strategy, criteria, order);
+    for poly in &basis {
+        let sugar = poly.terms.iter().map(|t| t.exponents.iter().sum::<usize>() as u32).max().unwrap_or(0);
+        queue.insert(lead(poly), sugar);
+    }
+
+    while let Some((i, j, sugar)) = queue.pop() {
+        let reduced = Polynomial::s_polynomial(&basis[i], &basis[j]).reduce(&basis);
+        queue.stats.reduced += 1;
+        if reduced.terms.is_empty() {
+            queue.stats.zero += 1;
+            continue;
+        }
+        queue.insert(lead(&reduced), sugar);
+        basis.push(reduced);
+    }
+
+    // a minimal basis from the active elements, each reduced by the others
+    let leads: Vec<VecExponent> = basis.iter().map(lead).collect();
+    let minimal: Vec<usize> = (0..basis.len()).filter(|&k| queue.is_active(k) && !(0..basis.len()).any(|m| {
+        m != k && queue.is_active(m) && leads[k].can_reduce(&leads[m]) && (m < k || leads[k] != leads[m])
+    })).collect();
+    let mut reduced_basis = Vec::with_capacity(minimal.len());
+    for &k in &minimal {
+        let others: Vec<Polynomial> = minimal.iter().filter(|&&m| m != k).map(|&m| basis[m].clone()).collect();
+        reduced_basis.push(basis[k].reduce(&others));
+    }
+    (reduced_basis, queue.stats)
+}
+
 pub fn are_bases_equivalent(set_a: Vec<Polynomial>, set_b: Vec<Polynomial>) -> bool {
     // Check if all polynomials in set_a reduce to zero using set_b
     for poly in &set_a {
@@ -338,12 +410,12 @@ fn main() {
         let num_polynomials: usize = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(3);
         let term_order: usize = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(0);
 
-        match term_order {
-            0 => TERM_ORDER.set(TermOrder::Lex).expect("TERM_ORDER already initialized"),
-            1 => TERM_ORDER.set(TermOrder::GrLex).expect("TERM_ORDER already initialized"),
-            2 => TERM_ORDER.set(TermOrder::RevLex).expect("TERM_ORDER already initialized"),
-            _ => TERM_ORDER.set(TermOrder::Lex).expect("TERM_ORDER already initialized"),
-        }
+        let order = match term_order {
+            0 => TermOrder::Lex,
+            1 => TermOrder::GrLex,
+            2 => TermOrder::RevLex,
+            _ => TermOrder::Lex,
+        };
 
         let mut input_basis = Vec::new();
         for _ in 0..num_polynomials {
@@ -357,7 +429,7 @@ fn main() {
                     exponents,
                 });
             }
-            input_basis.push(Polynomial::new(terms));
+            input_basis.push(Polynomial::with_order(terms, order));
         }
 
         let basis = naive_grobner_basis(input_basis);
@@ -371,7 +443,6 @@ fn main() {
     else { 
         if (n == 4) {   
             // Cyclic-4 benchmark
-            TERM_ORDER.set(TermOrder::Lex).expect("TERM_ORDER already initialized");
 
             // f1 = x0 + x1 + x2 + x3
             let p1 = Polynomial::new(vec![
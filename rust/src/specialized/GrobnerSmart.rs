@@ -1,11 +1,19 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use std::hash::{Hash, Hasher};
+use std::cmp::Ordering;
+use std::ops::Range;
+use crate::generic::bit_packed_exponent::BitPackedExponent;
+use crate::generic::i_exponent::IExponent;
+use crate::generic::monomial_order::MonomialOrder;
+use crate::generic::critical_pairs::{PairQueue, PairStats, PairStrategy};
 
-static mut TERM_ORDER: TermOrder = TermOrder::Lex; // default to lex order, can be set to GrLex or RevLex as well
 static mut MODULUS: u64 = 7; // default modulus for coefficients, can be changed as needed
 
 use crate::helpers::lcg::Lcg;
+use crate::gen_grobner::GrobnerError;
+use crate::generic::int_mod_p::{set_modulus, IntModP};
+use crate::gen_polynomial_parser::driver_system;
 
 
 #[derive(Clone, Debug, PartialEq)]
@@ -14,13 +22,29 @@ pub struct Term {
     pub exponents: u64, // Bitpacking of exponents, 8 bits per variable, 6 variables, last 16 bits for degree
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+// matched on in Term::compare, which works on the packed exponents directly, so weighted and
+// block orders are left to gen_grobner
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum TermOrder {
     Lex,
     GrLex,
     RevLex
 }
 
+// the orders of Term::compare on any exponent, for the PairQueue. RevLex here is GrLex with the
+// ties reversed
+impl MonomialOrder for TermOrder {
+    fn compare_vars<E: IExponent>(&self, a: &E, b: &E, vars: Range<usize>) -> Ordering {
+        let degree = |e: &E| vars.clone().map(|v| e.exponent(v)).sum::<u32>();
+        let lex_vars = |vars: Range<usize>| vars.map(|v| a.exponent(v).cmp(&b.exponent(v))).find(|o| o.is_ne()).unwrap_or(Ordering::Equal);
+        match self {
+            TermOrder::Lex => lex_vars(vars),
+            TermOrder::GrLex => degree(a).cmp(&degree(b)).then_with(|| lex_vars(vars)),
+            TermOrder::RevLex => degree(a).cmp(&degree(b)).then_with(|| lex_vars(vars).reverse()),
+        }
+    }
+}
+
 impl Term {
     /// Create a new Term with human-readable exponents (array of 6 variables) and degree computed automatically.
     pub fn from_exponents(coefficient: u64, exponents: [u8; 6]) -> Self {
@@ -86,8 +110,7 @@ impl Term {
         }
         Term::pack_exponents(er)
     } */
-    pub fn compare(&self, other: &Term) -> std::cmp::Ordering {
-        let order = unsafe { TERM_ORDER };
+    pub fn compare(&self, other: &Term, order: TermOrder) -> std::cmp::Ordering {
         match order {
             TermOrder::Lex => {
                 // Compare packed exponent fields directly (ignore degree)
@@ -184,9 +207,10 @@ impl Hash for Term {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct Polynomial {
     pub terms: Vec<Term>,
+    pub order: TermOrder,
 }
 
 impl Eq for Polynomial {
@@ -199,6 +223,13 @@ impl Hash for Polynomial {
     }
 }
 
+// the terms only, printed bases read as in the other ports whatever the order
+impl std::fmt::Debug for Polynomial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Polynomial").field("terms", &self.terms).finish()
+    }
+}
+
 fn mod_inverse(a: u64, m: u64) -> u64 {
     let (mut a, mut m) = (a as i64, m as i64);
     let (mut x0, mut x1) = (0i64, 1i64);
@@ -221,6 +252,20 @@ fn mod_inverse(a: u64, m: u64) -> u64 {
     (x1 % m0) as u64
 }
 
+// the top bit of each 8 bit exponent field
+const FIELD_TOPS: u64 = 0x0000_8080_8080_8080;
+
+// packed exponent sum, None when a field passes 255 or the degree passes 65535 instead of
+// carrying into the neighbouring field. The field top bits are added separately so the low
+// bits cannot carry across a boundary, a field overflows when its top bits carry out
+pub fn checked_add_exponents(a: u64, b: u64) -> Option<u64> {
+    let (low, degree_carry) = (a & !FIELD_TOPS).overflowing_add(b & !FIELD_TOPS);
+    if degree_carry || FIELD_TOPS & ((a & b) | ((a ^ b) & low)) != 0 {
+        return None;
+    }
+    Some(low ^ ((a ^ b) & FIELD_TOPS))
+}
+
 impl Polynomial {
     pub fn debug_print(&self) {
         println!("Polynomial:");
@@ -229,12 +274,17 @@ impl Polynomial {
         }
     }
 
-    pub fn new(mut terms: Vec<Term>) -> Self {
+    // in lex order, which all the drivers use
+    pub fn new(terms: Vec<Term>) -> Self {
+        Self::with_order(terms, TermOrder::Lex)
+    }
+
+    pub fn with_order(mut terms: Vec<Term>, order: TermOrder) -> Self {
         // Sort terms by sort order
-        terms.sort_by(|a, b| b.compare(a));
+        terms.sort_by(|a, b| b.compare(a, order));
         terms.retain(|t| t.coefficient != 0); // Remove zero coefficient terms
        
-        Polynomial { terms }
+        Polynomial { terms, order }
     }
 
     pub fn add(&self, other: &Polynomial) -> Polynomial {
@@ -259,7 +309,7 @@ impl Polynomial {
         }*/
 
 
-        Polynomial::new(result)
+        Polynomial::with_order(result, self.order)
     }
 
     pub fn subtract(&self, other: &Polynomial) -> Polynomial {
@@ -284,7 +334,7 @@ impl Polynomial {
             println!("Term: {:?}, Coefficient: {}", term.exponents, term.coefficient);
         }*/
 
-        Polynomial::new(result)
+        Polynomial::with_order(result, self.order)
     }
     
     pub fn make_monic(&self) -> Polynomial {
@@ -296,10 +346,10 @@ impl Polynomial {
             coefficient: (t.coefficient * inv) % modulus,
             exponents: t.exponents.clone(),
         }).collect();
-        Polynomial::new(new_terms)
+        Polynomial::with_order(new_terms, self.order)
     }
 
-   pub fn reduce(&self, divisors: &[Polynomial]) -> Polynomial {
+   pub fn reduce(&self, divisors: &[Polynomial]) -> Result<Polynomial, GrobnerError> {
         let modulus = unsafe {MODULUS};
         let mut result = self.clone(); // Start with the input polynomial
         let mut remainder: Vec<Term> = Vec::new();
@@ -324,7 +374,7 @@ impl Polynomial {
                                 exponents,
                             };
 
-                            let scaled_divisor = divisor.multiply_by_term(&reduction_term);
+                            let scaled_divisor = divisor.multiply_by_term(&reduction_term)?;
                             result = result.subtract(&scaled_divisor);
 
                             reduced = true;
@@ -348,24 +398,24 @@ impl Polynomial {
         }
 
         result.terms.append(&mut remainder);
-        Polynomial::new(result.terms)
+        Ok(Polynomial::with_order(result.terms, self.order))
     }
 
-    pub fn multiply_by_term(&self, term: &Term) -> Polynomial {
+    pub fn multiply_by_term(&self, term: &Term) -> Result<Polynomial, GrobnerError> {
         let modulus = unsafe {MODULUS};
         let terms = self
             .terms
             .iter()
-            .map(|t| Term {
+            .map(|t| Ok(Term {
                 coefficient: (t.coefficient * term.coefficient) % modulus,
-                exponents: t.exponents + term.exponents,
-            })
-            .collect();
+                exponents: checked_add_exponents(t.exponents, term.exponents).ok_or(GrobnerError::ExponentOverflow)?,
+            }))
+            .collect::<Result<Vec<Term>, GrobnerError>>()?;
 
-        Polynomial::new(terms)
+        Ok(Polynomial::with_order(terms, self.order))
     }
 
-    pub fn s_polynomial(p1: &Polynomial, p2: &Polynomial) -> Polynomial {
+    pub fn s_polynomial(p1: &Polynomial, p2: &Polynomial) -> Result<Polynomial, GrobnerError> {
         // Extract the leading terms of p1 and p2
         let leading_term_p1 = &p1.terms[0];
         let leading_term_p2 = &p2.terms[0];
@@ -379,19 +429,20 @@ impl Polynomial {
         let scaled_p1 = p1.multiply_by_term(&Term {
             coefficient: leading_term_p2.coefficient.clone(),
             exponents: scale_factor_p1,
-        });
+        })?;
 
         let scaled_p2 = p2.multiply_by_term(&Term {
             coefficient: leading_term_p1.coefficient.clone(),
             exponents: scale_factor_p2,
-        });
+        })?;
 
-        scaled_p1.subtract(&scaled_p2)
+        Ok(scaled_p1.subtract(&scaled_p2))
     }
 
 }
 
-pub fn naive_grobner_basis(polynomials: Vec<Polynomial>) -> Vec<Polynomial> {
+// fails when an S-polynomial or a reduction step overflows the 8 bit exponent fields
+pub fn naive_grobner_basis(polynomials: Vec<Polynomial>) -> Result<Vec<Polynomial>, GrobnerError> {
     let mut basis = polynomials.clone();
     let mut basis_set: HashSet<Polynomial> = HashSet::new();
     // print basis and polynomials
@@ -410,7 +461,7 @@ pub fn naive_grobner_basis(polynomials: Vec<Polynomial>) -> Vec<Polynomial> {
     while pairs.is_empty() == false {
         let (i, j) = pairs.remove(0);
         //println!("Processing pair ({}, {})", i, j);
-        let s_poly = Polynomial::s_polynomial(&basis[i], &basis[j]);
+        let s_poly = Polynomial::s_polynomial(&basis[i], &basis[j])?;
         /*let mut debug = false;
         if(i == 0 && j == 7 || i == 3 && j == 4 || j ==4 && i==3) 
         { debug = true; println!("Debugging S-Polynomial for basis[{}] and basis[{}]", i, j); 
@@ -419,7 +470,7 @@ pub fn naive_grobner_basis(polynomials: Vec<Polynomial>) -> Vec<Polynomial> {
                 println!("{:?}", poly);
             }
         }*/
-        let reduced = s_poly.reduce(&basis);
+        let reduced = s_poly.reduce(&basis)?;
         if !reduced.terms.is_empty() && !basis_set.contains(&reduced) {
             //println!("Adding new polynomial to basis."); 
             basis_set.insert(reduced.clone());
@@ -444,21 +495,190 @@ pub fn naive_grobner_basis(polynomials: Vec<Polynomial>) -> Vec<Polynomial> {
         // reduce poly by basis excluding itself
         let mut basis_excluding_self = basis.clone();
         basis_excluding_self.retain(|p| p != poly);
-        let reduced = poly.reduce(&basis_excluding_self);
+        let reduced = poly.reduce(&basis_excluding_self)?;
         if !reduced.terms.is_empty() && !reduced_basis.contains(&reduced) {
             reduced_basis.push(reduced.make_monic());
         }
     }
-    reduced_basis
+    Ok(reduced_basis)
     //basis
 
     
 }
 
+// Buchberger with the S-pairs in the generic PairQueue over the leading monomials, as
+// gen_grobner::buchberger_grobner_basis. Fifo without criteria reduces every pair
+pub fn buchberger_grobner_basis(polynomials: Vec<Polynomial>, strategy: PairStrategy, criteria: bool) -> Result<(Vec<Polynomial>, PairStats), GrobnerError> {
+    let lead = |poly: &Polynomial| BitPackedExponent::<6, 8>::new([poly.terms[0].exponents]);
+    let mut basis: Vec<Polynomial> = polynomials.into_iter().filter(|poly| !poly.terms.is_empty()).collect();
+    let Some(order) = basis.first().map(|poly| poly.order) else {
+        return Ok((Vec::new(), PairStats::default()));
+    };
+    let mut queue = PairQueue::new(strategy, criteria, order);
+    for poly in &basis {
+        let sugar = poly.terms.iter().map(|t| (t.exponents >> 48) as u32).max().unwrap_or(0);
+        queue.insert(lead(poly), sugar);
+    }
+
+    while let Some((i, j, sugar)) = queue.pop() {
+        let reduced = Polynomial::s_polynomial(&basis[i], &basis[j])?.reduce(&basis)?;
+        queue.stats.reduced += 1;
+        if reduced.terms.is_empty() {
+            queue.stats.zero += 1;
+            continue;
+        }
+        queue.insert(lead(&reduced), sugar);
+        basis.push(reduced);
+    }
+
+    Ok((interreduce(&basis, |k| queue.is_active(k))?, queue.stats))
+}
+
+// a minimal basis from the elements `active` keeps, each reduced by the others
+fn interreduce(basis: &[Polynomial], active: impl Fn(usize) -> bool) -> Result<Vec<Polynomial>, GrobnerError> {
+    let leads: Vec<BitPackedExponent<6, 8>> = basis.iter().map(|poly| BitPackedExponent::new([poly.terms[0].exponents])).collect();
+    let minimal: Vec<usize> = (0..basis.len()).filter(|&k| active(k) && !(0..basis.len()).any(|m| {
+        m != k && active(m) && leads[k].can_reduce(&leads[m]) && (m < k || leads[k] != leads[m])
+    })).collect();
+    let mut reduced_basis = Vec::with_capacity(minimal.len());
+    for &k in &minimal {
+        let others: Vec<Polynomial> = minimal.iter().filter(|&&m| m != k).map(|&m| basis[m].clone()).collect();
+        reduced_basis.push(basis[k].reduce(&others)?.make_monic());
+    }
+    Ok(reduced_basis)
+}
+
+// F4 on the packed exponents, as gen_grobner::f4_grobner_basis, with the Macaulay matrix in
+// u32 columns and coefficients mod MODULUS
+pub fn f4_grobner_basis(polynomials: Vec<Polynomial>, strategy: PairStrategy) -> Result<(Vec<Polynomial>, PairStats), GrobnerError> {
+    let modulus = unsafe {MODULUS};
+    let packed = |exponents: u64| BitPackedExponent::<6, 8>::new([exponents]);
+    let mut basis: Vec<Polynomial> = polynomials.into_iter().filter(|poly| !poly.terms.is_empty()).collect();
+    let Some(order) = basis.first().map(|poly| poly.order) else {
+        return Ok((Vec::new(), PairStats::default()));
+    };
+    let mut queue = PairQueue::new(strategy, true, order);
+    let mut leads = Vec::new();
+    for poly in &basis {
+        let sugar = poly.terms.iter().map(|t| (t.exponents >> 48) as u32).max().unwrap_or(0);
+        leads.push(packed(poly.terms[0].exponents));
+        queue.insert(packed(poly.terms[0].exponents), sugar);
+    }
+
+    loop {
+        let pairs = queue.pop_lowest_degree();
+        let Some(sugar) = pairs.iter().map(|&(_, _, sugar)| sugar).max() else {
+            break;
+        };
+        queue.stats.reduced += pairs.len();
+
+        // both halves of every pair, then a row for each monomial a leading monomial divides
+        let mut rows: Vec<Polynomial> = Vec::new();
+        let mut done: HashSet<u64> = HashSet::new();
+        let mut products: HashSet<(u64, usize)> = HashSet::new();
+        for &(i, j, _) in &pairs {
+            let lcm = basis[i].terms[0].LCM(&basis[j].terms[0]);
+            for k in [i, j] {
+                let multiplier = lcm - basis[k].terms[0].exponents;
+                if products.insert((multiplier, k)) {
+                    rows.push(basis[k].multiply_by_term(&Term { coefficient: 1, exponents: multiplier })?);
+                }
+            }
+            done.insert(lcm);
+        }
+        let mut next = 0;
+        while next < rows.len() {
+            let monomials: Vec<u64> = rows[next].terms.iter().map(|t| t.exponents).collect();
+            for monomial in monomials {
+                if !done.insert(monomial) {
+                    continue;
+                }
+                let divisor = packed(monomial);
+                if let Some(k) = (0..basis.len()).find(|&k| queue.is_active(k) && divisor.can_reduce(&leads[k])) {
+                    rows.push(basis[k].multiply_by_term(&Term { coefficient: 1, exponents: monomial - basis[k].terms[0].exponents })?);
+                }
+            }
+            next += 1;
+        }
+
+        let mut columns: Vec<u64> = done.into_iter().collect();
+        columns.sort_by(|&a, &b| Term { coefficient: 0, exponents: b }.compare(&Term { coefficient: 0, exponents: a }, order));
+        let column_of: HashMap<u64, u32> = columns.iter().enumerate().map(|(col, &monomial)| (monomial, col as u32)).collect();
+        let matrix = rows.iter().map(|row| row.terms.iter().map(|t| (column_of[&t.exponents], t.coefficient)).collect()).collect();
+
+        let found = eliminate_mod(matrix, columns.len(), modulus);
+        queue.stats.zero += pairs.len().saturating_sub(found.len());
+        for row in found {
+            let poly = Polynomial::with_order(row.into_iter().map(|(col, c)| Term { coefficient: c, exponents: columns[col as usize] }).collect(), order);
+            leads.push(packed(poly.terms[0].exponents));
+            queue.insert(packed(poly.terms[0].exponents), sugar);
+            basis.push(poly);
+        }
+    }
+
+    Ok((interreduce(&basis, |k| queue.is_active(k))?, queue.stats))
+}
+
+// SparseMatrix::eliminate on rows of (column, coefficient mod modulus): the sparsest row at each
+// leading column is its pivot, the rest are reduced in one dense accumulator and become pivots
+// where they survive. Returns the survivors, monic
+fn eliminate_mod(rows: Vec<Vec<(u32, u64)>>, columns: usize, modulus: u64) -> Vec<Vec<(u32, u64)>> {
+    let monic = |row: Vec<(u32, u64)>| -> Vec<(u32, u64)> {
+        let inverse = mod_inverse(row[0].1, modulus);
+        row.into_iter().map(|(col, c)| (col, c * inverse % modulus)).collect()
+    };
+    let mut pivots: Vec<Option<Vec<(u32, u64)>>> = vec![None; columns];
+    let mut rest = Vec::new();
+    let mut rows: Vec<Vec<(u32, u64)>> = rows.into_iter().filter(|row| !row.is_empty()).collect();
+    rows.sort_by_key(|row| (row[0].0, row.len()));
+    for row in rows {
+        let lead = row[0].0 as usize;
+        match pivots[lead] {
+            None => pivots[lead] = Some(monic(row)),
+            Some(_) => rest.push(row),
+        }
+    }
+
+    let mut found = Vec::new();
+    let mut dense = vec![0u64; columns];
+    for row in rest {
+        let first = row[0].0 as usize;
+        for (col, c) in row {
+            dense[col as usize] = c;
+        }
+        let mut reduced = Vec::new();
+        for col in first..columns {
+            let c = std::mem::take(&mut dense[col]);
+            if c == 0 {
+                continue;
+            }
+            match &pivots[col] {
+                Some(pivot) => {
+                    let factor = modulus - c;
+                    for &(pcol, pc) in &pivot[1..] {
+                        let entry = &mut dense[pcol as usize];
+                        *entry = (*entry + factor * pc) % modulus;
+                    }
+                }
+                None => reduced.push((col as u32, c)),
+            }
+        }
+        if let Some(&(lead, _)) = reduced.first() {
+            let reduced = monic(reduced);
+            pivots[lead as usize] = Some(reduced.clone());
+            found.push(reduced);
+        }
+    }
+    found
+}
+
 pub fn are_bases_equivalent(set_a: Vec<Polynomial>, set_b: Vec<Polynomial>, modulus: u64) -> bool {
     // Check if all polynomials in set_a reduce to zero using set_b
     for poly in &set_a {
-        let reduced = poly.reduce(&set_b);
+        let reduced = match poly.reduce(&set_b) {
+            Ok(reduced) => reduced,
+            Err(_) => return false, // overflowed, equivalence not shown
+        };
         if !reduced.terms.is_empty() {
             return false; // Found a polynomial in set_a that does not reduce to zero
         }
@@ -466,7 +686,10 @@ pub fn are_bases_equivalent(set_a: Vec<Polynomial>, set_b: Vec<Polynomial>, modu
 
     // Check if all polynomials in set_b reduce to zero using set_a
     for poly in &set_b {
-        let reduced = poly.reduce(&set_a);
+        let reduced = match poly.reduce(&set_a) {
+            Ok(reduced) => reduced,
+            Err(_) => return false,
+        };
         if !reduced.terms.is_empty() {
             return false; // Found a polynomial in set_b that does not reduce to zero
         }
@@ -488,12 +711,12 @@ fn main() {
         let num_polynomials: usize = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(3);
         let term_order: usize = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(0);
 
-        match term_order {
-            0 => unsafe {TERM_ORDER = TermOrder::Lex},
-            1 => unsafe {TERM_ORDER = TermOrder::GrLex},
-            2 => unsafe {TERM_ORDER = TermOrder::RevLex},
-            _ => unsafe {TERM_ORDER = TermOrder::Lex},
-        }
+        let order = match term_order {
+            0 => TermOrder::Lex,
+            1 => TermOrder::GrLex,
+            2 => TermOrder::RevLex,
+            _ => TermOrder::Lex,
+        };
 
         let mut input_basis = Vec::new();
         let modulus = 13 as u64;
@@ -507,11 +730,13 @@ fn main() {
                 }
                 terms.push(Term::from_exponents(coefficient, exponents));
             }
-            input_basis.push(Polynomial::new(terms));
+            input_basis.push(Polynomial::with_order(terms, order));
         }
 
-        let basis = naive_grobner_basis(input_basis);
-        println!("{}", basis.len());
+        match naive_grobner_basis(input_basis) {
+            Ok(basis) => println!("{}", basis.len()),
+            Err(e) => println!("Grobner basis failed: {}", e),
+        }
         /*println!("Computed Grobner Basis Polynomials:");
         for poly in &basis {
             poly.debug_print();
@@ -520,168 +745,47 @@ fn main() {
         return;
     }
     else {
-        let modulus = 7 as u64;
         let args: Vec<String> = std::env::args().collect();
         let n = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(4);
-        unsafe { TERM_ORDER = TermOrder::Lex; }
-
-        if n == 4 {
-            println!("Rust specialized finite coeff bitpacked cyclic 4");
-            // Cyclic 4 system: x0+x1+x2+x3, x0x1+x1x2+x2x3+x3x0, x0x1x2+x1x2x3+x2x3x0+x3x0x1, x0x1x2x3-1
-            // f1 = x0 + x1 + x2 + x3
-            let p1 = Polynomial::new(vec![
-                Term::from_exponents(1, [1, 0, 0, 0, 0, 0]),
-                Term::from_exponents(1, [0, 1, 0, 0, 0, 0]),
-                Term::from_exponents(1, [0, 0, 1, 0, 0, 0]),
-                Term::from_exponents(1, [0, 0, 0, 1, 0, 0]),
-            ]);
-
-            // f2 = x0x1 + x1x2 + x2x3 + x3x0
-            let p2 = Polynomial::new(vec![
-                Term::from_exponents(1, [1, 1, 0, 0, 0, 0]),
-                Term::from_exponents(1, [0, 1, 1, 0, 0, 0]),
-                Term::from_exponents(1, [0, 0, 1, 1, 0, 0]),
-                Term::from_exponents(1, [1, 0, 0, 1, 0, 0]),
-            ]);
-
-            // f3 = x0x1x2 + x1x2x3 + x2x3x0 + x3x0x1
-            let p3 = Polynomial::new(vec![
-                Term::from_exponents(1, [1, 1, 1, 0, 0, 0]),
-                Term::from_exponents(1, [0, 1, 1, 1, 0, 0]),
-                Term::from_exponents(1, [1, 0, 1, 1, 0, 0]),
-                Term::from_exponents(1, [1, 1, 0, 1, 0, 0]),
-            ]);
-
-            // f4 = x0x1x2x3 - 1
-            let p4 = Polynomial::new(vec![
-                Term::from_exponents(1, [1, 1, 1, 1, 0, 0]),
-                Term::from_exponents(modulus-1, [0, 0, 0, 0, 0, 0]),
-            ]);
-            let start = vec![p1,p2,p3,p4];
-            for i in 0..10 {
-                let basis = naive_grobner_basis(start.clone());
-                println!("Iteration {}: complete", i);
-                if i == 9 {
-                    println!("Final Grobner Basis:");
-                    for poly in &basis {
-                        poly.debug_print();
-                        println!("---");
-                    }
-                }
-
+        // arg1: n or cyclic:<n> for cyclic n, otherwise a system file, - for stdin. A file named
+        //       like a number needs its path, ./5
+        set_modulus(unsafe { MODULUS });
+        let (name, system) = match driver_system::<IntModP>(args.get(1).map(String::as_str), n) {
+            Ok(system) => system,
+            Err(e) => {
+                println!("{}", e);
+                return;
             }
+        };
+        if system.variables.len() > 6 || system.max_exponent() > 255 {
+            println!("{} does not fit the six 8 bit exponents of the bitpacked monomial", name);
+            return;
         }
-        else if n == 5 {
-            println!("Rust specialized finite coeff bitpacked cyclic 5");
-            // Cyclic 5
-            let p1 = Polynomial::new(vec![
-                Term::from_exponents(1, [1, 0, 0, 0, 0, 0]),
-                Term::from_exponents(1, [0, 1, 0, 0, 0, 0]),
-                Term::from_exponents(1, [0, 0, 1, 0, 0, 0]),
-                Term::from_exponents(1, [0, 0, 0, 1, 0, 0]),
-                Term::from_exponents(1, [0, 0, 0, 0, 1, 0]),
-            ]);
-            let p2 = Polynomial::new(vec![
-                Term::from_exponents(1, [1, 1, 0, 0, 0, 0]),
-                Term::from_exponents(1, [0, 1, 1, 0, 0, 0]),
-                Term::from_exponents(1, [0, 0, 1, 1, 0, 0]),
-                Term::from_exponents(1, [0, 0, 0, 1, 1, 0]),
-                Term::from_exponents(1, [1, 0, 0, 0, 1, 0]),
-            ]);
-            let p3 = Polynomial::new(vec![
-                Term::from_exponents(1, [1, 1, 1, 0, 0, 0]),
-                Term::from_exponents(1, [0, 1, 1, 1, 0, 0]),
-                Term::from_exponents(1, [0, 0, 1, 1, 1, 0]),
-                Term::from_exponents(1, [1, 0, 0, 1, 1, 0]),
-                Term::from_exponents(1, [1, 1, 0, 0, 1, 0]),
-            ]);
-            let p4 = Polynomial::new(vec![
-                Term::from_exponents(1, [1, 1, 1, 1, 0, 0]),
-                Term::from_exponents(1, [0, 1, 1, 1, 1, 0]),
-                Term::from_exponents(1, [1, 0, 1, 1, 1, 0]),
-                Term::from_exponents(1, [1, 1, 0, 1, 1, 0]),
-                Term::from_exponents(1, [1, 1, 1, 0, 1, 0]),
-            ]);
-            let p5 = Polynomial::new(vec![
-                Term::from_exponents(1, [1, 1, 1, 1, 1, 0]),
-                Term::from_exponents(modulus-1, [0, 0, 0, 0, 0, 0]),
-            ]);
-            let start = vec![p1,p2,p3,p4,p5];
-            for i in 0..10 {
-                let basis = naive_grobner_basis(start.clone());
-                println!("Iteration {}: complete", i);
-                if i == 9 {
-                    println!("Final Grobner Basis:");
-                    for poly in &basis {
-                        poly.debug_print();
-                        println!("---");
-                    }
-                }
+        println!("Rust specialized finite coeff bitpacked {}", name);
+        let start: Vec<Polynomial> = system.polynomials.iter().map(|terms| Polynomial::new(terms.iter().map(|(exps, c)| {
+            let mut exponents = [0u8; 6];
+            for (packed, &e) in exponents.iter_mut().zip(exps) {
+                *packed = e as u8;
             }
-        }
-        else if n == 6 {
-            println!("Rust specialized finite coeff bitpacked cyclic 6");
-            // Cyclic 6
-            let p1 = Polynomial::new(vec![
-                Term::from_exponents(1, [1, 0, 0, 0, 0, 0]),
-                Term::from_exponents(1, [0, 1, 0, 0, 0, 0]),
-                Term::from_exponents(1, [0, 0, 1, 0, 0, 0]),
-                Term::from_exponents(1, [0, 0, 0, 1, 0, 0]),
-                Term::from_exponents(1, [0, 0, 0, 0, 1, 0]),
-                Term::from_exponents(1, [0, 0, 0, 0, 0, 1]),
-            ]);
-             let p2 = Polynomial::new(vec![
-                Term::from_exponents(1, [1, 1, 0, 0, 0, 0]),
-                Term::from_exponents(1, [0, 1, 1, 0, 0, 0]),
-                Term::from_exponents(1, [0, 0, 1, 1, 0, 0]),
-                Term::from_exponents(1, [0, 0, 0, 1, 1, 0]),
-                Term::from_exponents(1, [0, 0, 0, 0, 1, 1]),
-                Term::from_exponents(1, [1 ,0 ,0 ,0 ,0 ,1]),
-            ]);
-             let p3 = Polynomial::new(vec![
-                Term::from_exponents(1,[1 ,1 ,1 ,0 ,0 ,0]),
-                Term::from_exponents(1,[0 ,1 ,1 ,1 ,0 ,0]),
-                Term::from_exponents(1,[0 ,0 ,1 ,1 ,1 ,0]),
-                Term::from_exponents(1,[0 ,0 ,0 ,1 ,1 ,1]),
-                Term::from_exponents(1,[1 ,0 ,0 ,0 ,1 ,1]),
-                Term::from_exponents(1,[1 ,1 ,0 ,0 ,0 ,1]),
-            ]);
-             let p4 = Polynomial::new(vec![
-                Term::from_exponents(1,[1 ,1 ,1 ,1 ,0 ,0]),
-                Term::from_exponents(1,[0 ,1 ,1 ,1 ,1 ,0]),
-                Term::from_exponents(1,[0 ,0 ,1 ,1 ,1 ,1]),
-                Term::from_exponents(1,[1 ,0 ,0 ,1 ,1 ,1]),
-                Term::from_exponents(1,[1 ,1 ,0 ,0 ,1 ,1]),
-                Term::from_exponents(1,[1 ,1 ,1 ,0 ,0 ,1]),
-            ]);
-             let p5 = Polynomial::new(vec![
-                Term::from_exponents(1,[1 ,1 ,1 ,1 ,1 ,0]),
-                Term::from_exponents(1,[0 ,1 ,1 ,1 ,1 ,1]),
-                Term::from_exponents(1,[1 ,0 ,1 ,1 ,1 ,1]),
-                Term::from_exponents(1,[1 ,1 ,0 ,1 ,1 ,1]),
-                Term::from_exponents(1,[1 ,1 ,1 ,0 ,1 ,1]),
-                Term::from_exponents(1,[1 ,1 ,1 ,1 ,0 ,1]),
-            ]);
-             let p6 = Polynomial::new(vec![
-                Term::from_exponents(1,[1 ,1 ,1 ,1 ,1 ,1]),
-                Term::from_exponents(modulus-1,[0 ,0 ,0 ,0 ,0 ,0]),
-            ]);
-            let start = vec![p1,p2,p3,p4,p5,p6];
-            for i in 0..10 {
-                let basis = naive_grobner_basis(start.clone());
-                println!("Iteration {}: complete", i);
-                if i == 9 {
-                    println!("Final Grobner Basis:");
-                    for poly in &basis {
-                        poly.debug_print();
-                        println!("---");
-                    }
+            Term::from_exponents(c.i, exponents)
+        }).collect())).collect();
+        for i in 0..10 {
+            let basis = match naive_grobner_basis(start.clone()) {
+                Ok(basis) => basis,
+                Err(e) => {
+                    println!("Iteration {}: failed, {}", i, e);
+                    continue;
+                }
+            };
+            println!("Iteration {}: complete", i);
+            if i == 9 {
+                println!("Final Grobner Basis:");
+                for poly in &basis {
+                    poly.debug_print();
+                    println!("---");
                 }
             }
         }
-        else if n == 7 {
-            //bitpacked cant fit 7 variables
-        }
     }
 }
 #[allow(dead_code)]